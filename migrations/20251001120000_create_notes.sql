@@ -0,0 +1,14 @@
+-- Create notes table for per-book notes
+CREATE TABLE notes (
+    id SERIAL PRIMARY KEY,
+    book_id INTEGER NOT NULL REFERENCES books(id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    page_number INTEGER CHECK (page_number >= 0),
+    chapter TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Add indexes for better query performance
+CREATE INDEX idx_notes_book_id ON notes(book_id);
+CREATE INDEX idx_notes_created_at ON notes(created_at);
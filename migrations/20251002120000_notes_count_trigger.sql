@@ -0,0 +1,35 @@
+-- Keep books.notes_count in sync with the notes table
+CREATE OR REPLACE FUNCTION sync_book_notes_count() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        UPDATE books SET notes_count = COALESCE(notes_count, 0) + 1 WHERE id = NEW.book_id;
+    END IF;
+
+    IF TG_OP IN ('DELETE', 'UPDATE') THEN
+        UPDATE books SET notes_count = GREATEST(COALESCE(notes_count, 0) - 1, 0) WHERE id = OLD.book_id;
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_notes_insert_count
+    AFTER INSERT ON notes
+    FOR EACH ROW EXECUTE FUNCTION sync_book_notes_count();
+
+CREATE TRIGGER trg_notes_delete_count
+    AFTER DELETE ON notes
+    FOR EACH ROW EXECUTE FUNCTION sync_book_notes_count();
+
+-- Moving a note between books decrements the old book and increments the new one
+CREATE TRIGGER trg_notes_move_count
+    AFTER UPDATE OF book_id ON notes
+    FOR EACH ROW
+    WHEN (OLD.book_id IS DISTINCT FROM NEW.book_id)
+    EXECUTE FUNCTION sync_book_notes_count();
+
+-- Backfill existing counts
+UPDATE books SET notes_count = (SELECT COUNT(*) FROM notes WHERE notes.book_id = books.id);
+
+ALTER TABLE books
+    ALTER COLUMN notes_count SET NOT NULL;
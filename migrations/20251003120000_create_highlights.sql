@@ -0,0 +1,21 @@
+-- Create ENUM type for highlight color
+CREATE TYPE highlight_color AS ENUM ('yellow', 'blue', 'pink', 'orange');
+
+-- Create highlights table for quotes anchored to a position in a book
+CREATE TABLE highlights (
+    id SERIAL PRIMARY KEY,
+    book_id INTEGER NOT NULL REFERENCES books(id) ON DELETE CASCADE,
+    text TEXT NOT NULL,
+    page_number INTEGER CHECK (page_number >= 0),
+    chapter TEXT,
+    percent DOUBLE PRECISION CHECK (percent >= 0 AND percent <= 100),
+    location_start INTEGER CHECK (location_start >= 0),
+    location_end INTEGER CHECK (location_end >= location_start),
+    color highlight_color NOT NULL DEFAULT 'yellow',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Add indexes for better query performance
+CREATE INDEX idx_highlights_book_id ON highlights(book_id);
+CREATE INDEX idx_highlights_position ON highlights(book_id, location_start, page_number, percent);
@@ -0,0 +1,15 @@
+-- Track imported clippings so re-importing the same file does not duplicate them
+ALTER TABLE highlights
+    ADD COLUMN import_key TEXT;
+
+ALTER TABLE notes
+    ADD COLUMN import_key TEXT;
+
+CREATE UNIQUE INDEX idx_highlights_import_key ON highlights(import_key);
+CREATE UNIQUE INDEX idx_notes_import_key ON notes(import_key);
+
+-- Support matching books by normalized title and author
+CREATE INDEX idx_books_normalized_title_author ON books(
+    regexp_replace(lower(btrim(title)), '\s+', ' ', 'g'),
+    regexp_replace(lower(btrim(author)), '\s+', ' ', 'g')
+);
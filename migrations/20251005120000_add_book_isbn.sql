@@ -0,0 +1,7 @@
+-- Add ISBN columns so imports can match books reliably
+ALTER TABLE books
+    ADD COLUMN isbn TEXT,
+    ADD COLUMN isbn13 TEXT;
+
+CREATE INDEX idx_books_isbn ON books(isbn);
+CREATE INDEX idx_books_isbn13 ON books(isbn13);
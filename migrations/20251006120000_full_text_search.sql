@@ -0,0 +1,22 @@
+-- Full-text search vectors, weighted so title and author matches rank first
+ALTER TABLE books
+    ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', coalesce(title, '')), 'A') ||
+        setweight(to_tsvector('english', coalesce(author, '')), 'A') ||
+        setweight(to_tsvector('english', coalesce(description, '')), 'C')
+    ) STORED;
+
+ALTER TABLE notes
+    ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+        to_tsvector('english', coalesce(chapter, '') || ' ' || content)
+    ) STORED;
+
+ALTER TABLE highlights
+    ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+        to_tsvector('english', coalesce(chapter, '') || ' ' || text)
+    ) STORED;
+
+-- Add indexes for better query performance
+CREATE INDEX idx_books_search_vector ON books USING GIN(search_vector);
+CREATE INDEX idx_notes_search_vector ON notes USING GIN(search_vector);
+CREATE INDEX idx_highlights_search_vector ON highlights USING GIN(search_vector);
@@ -0,0 +1,5 @@
+-- Trigram indexes for typo-tolerant title and author search
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX idx_books_title_trgm ON books USING GIN(title gin_trgm_ops);
+CREATE INDEX idx_books_author_trgm ON books USING GIN(author gin_trgm_ops);
@@ -0,0 +1,24 @@
+-- Create collections table for user-defined, manually ordered shelves
+CREATE TABLE collections (
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    position INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Books in a collection, in the collection's own order
+CREATE TABLE collection_books (
+    collection_id INTEGER NOT NULL REFERENCES collections(id) ON DELETE CASCADE,
+    book_id INTEGER NOT NULL REFERENCES books(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL DEFAULT 0,
+    added_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (collection_id, book_id)
+);
+
+-- Add indexes for better query performance
+CREATE UNIQUE INDEX idx_collections_name ON collections(lower(name));
+CREATE INDEX idx_collections_position ON collections(position);
+CREATE INDEX idx_collection_books_book_id ON collection_books(book_id);
+CREATE INDEX idx_collection_books_position ON collection_books(collection_id, position);
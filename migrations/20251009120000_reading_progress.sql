@@ -0,0 +1,17 @@
+-- Add book length so progress can be expressed as a percentage
+ALTER TABLE books
+    ADD COLUMN page_count INTEGER CHECK (page_count > 0),
+    ADD COLUMN audio_minutes INTEGER CHECK (audio_minutes > 0);
+
+-- Create reading_progress table with one row per progress update
+CREATE TABLE reading_progress (
+    id SERIAL PRIMARY KEY,
+    book_id INTEGER NOT NULL REFERENCES books(id) ON DELETE CASCADE,
+    current_page INTEGER CHECK (current_page >= 0),
+    percent DOUBLE PRECISION CHECK (percent >= 0 AND percent <= 100),
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (current_page IS NOT NULL OR percent IS NOT NULL)
+);
+
+-- Add indexes for better query performance
+CREATE INDEX idx_reading_progress_book_id ON reading_progress(book_id, recorded_at);
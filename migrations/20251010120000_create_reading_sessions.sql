@@ -0,0 +1,16 @@
+-- Create reading_sessions table with one row per sitting with a book
+CREATE TABLE reading_sessions (
+    id SERIAL PRIMARY KEY,
+    book_id INTEGER NOT NULL REFERENCES books(id) ON DELETE CASCADE,
+    started_at TIMESTAMPTZ NOT NULL,
+    ended_at TIMESTAMPTZ NOT NULL CHECK (ended_at >= started_at),
+    pages_read INTEGER CHECK (pages_read >= 0),
+    location TEXT,
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Add indexes for better query performance
+CREATE INDEX idx_reading_sessions_book_id ON reading_sessions(book_id, started_at);
+CREATE INDEX idx_reading_sessions_started_at ON reading_sessions(started_at);
@@ -0,0 +1,64 @@
+-- Create ENUM type for the edition a book was read in
+CREATE TYPE book_format AS ENUM ('print', 'ebook', 'audiobook');
+
+-- Create read_throughs table so a book can be read, and finished, more than once
+CREATE TABLE read_throughs (
+    id SERIAL PRIMARY KEY,
+    book_id INTEGER NOT NULL REFERENCES books(id) ON DELETE CASCADE,
+    started_on DATE,
+    finished_on DATE CHECK (finished_on >= started_on),
+    rating INTEGER CHECK (rating >= 1 AND rating <= 5),
+    format book_format,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Add indexes for better query performance
+CREATE INDEX idx_read_throughs_book_id ON read_throughs(book_id);
+
+-- At most one read-through per book can be in progress
+CREATE UNIQUE INDEX idx_read_throughs_in_progress ON read_throughs(book_id) WHERE finished_on IS NULL;
+
+-- Backfill before the triggers exist so current statuses are kept as they are
+INSERT INTO read_throughs (book_id, finished_on, rating)
+SELECT id, date_finished, rating FROM books WHERE date_finished IS NOT NULL;
+
+INSERT INTO read_throughs (book_id)
+SELECT id FROM books WHERE status = 'reading';
+
+-- Derive books.status and books.date_finished from the book's latest read-through.
+-- An unfinished read-through is always the latest; books without any are left alone.
+CREATE OR REPLACE FUNCTION refresh_book_reading_state(target_book_id INTEGER) RETURNS VOID AS $$
+BEGIN
+    UPDATE books
+    SET status = CASE WHEN latest.finished_on IS NULL THEN 'reading'::book_status ELSE 'finished'::book_status END,
+        date_finished = latest.last_finished_on
+    FROM (
+        SELECT finished_on,
+               (SELECT MAX(finished_on) FROM read_throughs WHERE book_id = target_book_id) AS last_finished_on
+        FROM read_throughs
+        WHERE book_id = target_book_id
+        ORDER BY finished_on IS NULL DESC, COALESCE(finished_on, started_on) DESC NULLS LAST, id DESC
+        LIMIT 1
+    ) AS latest
+    WHERE books.id = target_book_id;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION sync_book_reading_state() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        PERFORM refresh_book_reading_state(NEW.book_id);
+    END IF;
+
+    IF TG_OP = 'DELETE' OR (TG_OP = 'UPDATE' AND OLD.book_id IS DISTINCT FROM NEW.book_id) THEN
+        PERFORM refresh_book_reading_state(OLD.book_id);
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_read_throughs_reading_state
+    AFTER INSERT OR UPDATE OR DELETE ON read_throughs
+    FOR EACH ROW EXECUTE FUNCTION sync_book_reading_state();
@@ -0,0 +1,33 @@
+-- Add the day the current read began
+ALTER TABLE books
+    ADD COLUMN date_started DATE;
+
+-- Derive books.date_started from the latest read-through as well
+CREATE OR REPLACE FUNCTION refresh_book_reading_state(target_book_id INTEGER) RETURNS VOID AS $$
+BEGIN
+    UPDATE books
+    SET status = CASE WHEN latest.finished_on IS NULL THEN 'reading'::book_status ELSE 'finished'::book_status END,
+        date_started = latest.started_on,
+        date_finished = latest.last_finished_on
+    FROM (
+        SELECT started_on,
+               finished_on,
+               (SELECT MAX(finished_on) FROM read_throughs WHERE book_id = target_book_id) AS last_finished_on
+        FROM read_throughs
+        WHERE book_id = target_book_id
+        ORDER BY finished_on IS NULL DESC, COALESCE(finished_on, started_on) DESC NULLS LAST, id DESC
+        LIMIT 1
+    ) AS latest
+    WHERE books.id = target_book_id;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Backfill start dates already recorded on read-throughs
+UPDATE books
+SET date_started = latest.started_on
+FROM (
+    SELECT DISTINCT ON (book_id) book_id, started_on
+    FROM read_throughs
+    ORDER BY book_id, finished_on IS NULL DESC, COALESCE(finished_on, started_on) DESC NULLS LAST, id DESC
+) AS latest
+WHERE books.id = latest.book_id;
@@ -0,0 +1,35 @@
+-- Books can be put down for a while or given up on
+ALTER TYPE book_status ADD VALUE IF NOT EXISTS 'paused';
+ALTER TYPE book_status ADD VALUE IF NOT EXISTS 'abandoned';
+
+-- Why and where an abandoned book was given up
+ALTER TABLE books
+    ADD COLUMN abandon_reason TEXT,
+    ADD COLUMN abandon_page INTEGER CHECK (abandon_page > 0);
+
+-- A read-through in progress keeps a paused or abandoned status instead of
+-- forcing the book back to reading; finishing one clears the abandonment
+CREATE OR REPLACE FUNCTION refresh_book_reading_state(target_book_id INTEGER) RETURNS VOID AS $$
+BEGIN
+    UPDATE books
+    SET status = CASE
+            WHEN latest.finished_on IS NOT NULL THEN 'finished'::book_status
+            WHEN books.status::text IN ('paused', 'abandoned') THEN books.status
+            ELSE 'reading'::book_status
+        END,
+        abandon_reason = CASE WHEN latest.finished_on IS NULL THEN books.abandon_reason END,
+        abandon_page = CASE WHEN latest.finished_on IS NULL THEN books.abandon_page END,
+        date_started = latest.started_on,
+        date_finished = latest.last_finished_on
+    FROM (
+        SELECT started_on,
+               finished_on,
+               (SELECT MAX(finished_on) FROM read_throughs WHERE book_id = target_book_id) AS last_finished_on
+        FROM read_throughs
+        WHERE book_id = target_book_id
+        ORDER BY finished_on IS NULL DESC, COALESCE(finished_on, started_on) DESC NULLS LAST, id DESC
+        LIMIT 1
+    ) AS latest
+    WHERE books.id = target_book_id;
+END;
+$$ LANGUAGE plpgsql;
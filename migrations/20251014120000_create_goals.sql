@@ -0,0 +1,19 @@
+-- Create ENUM types for how long a goal runs and what it counts
+CREATE TYPE goal_period AS ENUM ('yearly', 'monthly');
+CREATE TYPE goal_metric AS ENUM ('books', 'pages');
+
+-- Create goals table: read `target` books or pages in a year or month
+CREATE TABLE goals (
+    id SERIAL PRIMARY KEY,
+    period goal_period NOT NULL,
+    metric goal_metric NOT NULL,
+    year INTEGER NOT NULL CHECK (year >= 1 AND year <= 9999),
+    month INTEGER CHECK (month >= 1 AND month <= 12),
+    target INTEGER NOT NULL CHECK (target > 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((period = 'monthly') = (month IS NOT NULL))
+);
+
+-- One goal per metric and period
+CREATE UNIQUE INDEX idx_goals_period ON goals(metric, year, COALESCE(month, 0));
@@ -0,0 +1,37 @@
+-- A book whose last read-through is deleted goes back to the wishlist instead
+-- of keeping the status and dates derived from read-throughs that are gone
+CREATE OR REPLACE FUNCTION refresh_book_reading_state(target_book_id INTEGER) RETURNS VOID AS $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM read_throughs WHERE book_id = target_book_id) THEN
+        UPDATE books
+        SET status = 'wishlist'::book_status,
+            date_started = NULL,
+            date_finished = NULL,
+            abandon_reason = NULL,
+            abandon_page = NULL
+        WHERE id = target_book_id;
+        RETURN;
+    END IF;
+
+    UPDATE books
+    SET status = CASE
+            WHEN latest.finished_on IS NOT NULL THEN 'finished'::book_status
+            WHEN books.status::text IN ('paused', 'abandoned') THEN books.status
+            ELSE 'reading'::book_status
+        END,
+        abandon_reason = CASE WHEN latest.finished_on IS NULL THEN books.abandon_reason END,
+        abandon_page = CASE WHEN latest.finished_on IS NULL THEN books.abandon_page END,
+        date_started = latest.started_on,
+        date_finished = latest.last_finished_on
+    FROM (
+        SELECT started_on,
+               finished_on,
+               (SELECT MAX(finished_on) FROM read_throughs WHERE book_id = target_book_id) AS last_finished_on
+        FROM read_throughs
+        WHERE book_id = target_book_id
+        ORDER BY finished_on IS NULL DESC, COALESCE(finished_on, started_on) DESC NULLS LAST, id DESC
+        LIMIT 1
+    ) AS latest
+    WHERE books.id = target_book_id;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,45 @@
+-- Repair books whose status contradicts their dates, which updates that did not
+-- touch the reading state used to write back unchecked
+
+-- Finished books without a finish date: complete the read in progress, or log
+-- one, on the last date known for the book. The read-through triggers then
+-- derive date_finished.
+UPDATE read_throughs
+SET finished_on = COALESCE(read_throughs.started_on, books.date_started, books.date_added),
+    updated_at = NOW()
+FROM books
+WHERE read_throughs.book_id = books.id
+  AND read_throughs.finished_on IS NULL
+  AND books.status = 'finished'
+  AND books.date_finished IS NULL;
+
+INSERT INTO read_throughs (book_id, started_on, finished_on, rating)
+SELECT id, date_started, COALESCE(date_started, date_added), rating
+FROM books
+WHERE status = 'finished'
+  AND date_finished IS NULL
+  AND NOT EXISTS (SELECT 1 FROM read_throughs WHERE book_id = books.id);
+
+SELECT refresh_book_reading_state(id)
+FROM books
+WHERE status = 'finished' AND date_finished IS NULL;
+
+-- Wishlist books have no read in progress, and a book finished before moves
+-- back to finished
+DELETE FROM read_throughs
+USING books
+WHERE read_throughs.book_id = books.id
+  AND read_throughs.finished_on IS NULL
+  AND books.status = 'wishlist';
+
+SELECT refresh_book_reading_state(id)
+FROM books
+WHERE status = 'wishlist'
+  AND EXISTS (SELECT 1 FROM read_throughs WHERE book_id = books.id);
+
+UPDATE books
+SET date_started = NULL,
+    date_finished = NULL
+WHERE status = 'wishlist'
+  AND (date_started IS NOT NULL OR date_finished IS NOT NULL)
+  AND NOT EXISTS (SELECT 1 FROM read_throughs WHERE book_id = books.id);
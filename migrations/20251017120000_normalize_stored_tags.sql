@@ -0,0 +1,35 @@
+-- Bring tags written before normalization into canonical form: each level is
+-- trimmed, lower-cased and has whitespace collapsed, leading '#' and empty
+-- levels are dropped, and duplicates are removed keeping the first position.
+-- Configured aliases are applied by the server at startup.
+WITH cleaned AS (
+    SELECT id,
+           ARRAY(
+               SELECT tag
+               FROM (
+                   SELECT array_to_string(ARRAY(
+                              SELECT cleaned_level
+                              FROM (
+                                  SELECT btrim(regexp_replace(lower(level), '\s+', ' ', 'g')) AS cleaned_level,
+                                         level_position
+                                  FROM unnest(string_to_array(ltrim(btrim(raw_tag), '#'), '/'))
+                                       WITH ORDINALITY AS l(level, level_position)
+                              ) AS levels
+                              WHERE cleaned_level <> ''
+                              ORDER BY level_position
+                          ), '/') AS tag,
+                          position
+                   FROM unnest(books.tags) WITH ORDINALITY AS t(raw_tag, position)
+               ) AS cleaned_tags
+               WHERE tag <> ''
+               GROUP BY tag
+               ORDER BY MIN(position)
+           ) AS tags
+    FROM books
+    WHERE tags IS NOT NULL
+)
+UPDATE books
+SET tags = cleaned.tags
+FROM cleaned
+WHERE books.id = cleaned.id
+  AND books.tags IS DISTINCT FROM cleaned.tags;
@@ -0,0 +1,15 @@
+-- Every tag on a book together with each of its ancestors, so `fiction/sci-fi`
+-- is also found under `fiction` by the exact, GIN-indexed array operators
+CREATE FUNCTION tag_paths(tags TEXT[]) RETURNS TEXT[] AS $$
+    SELECT ARRAY(
+        SELECT DISTINCT array_to_string(levels[1:depth], '/')
+        FROM unnest(tags) AS tag,
+             string_to_array(tag, '/') AS levels,
+             generate_series(1, cardinality(levels)) AS depth
+    )
+$$ LANGUAGE SQL IMMUTABLE;
+
+ALTER TABLE books
+    ADD COLUMN tag_paths TEXT[] GENERATED ALWAYS AS (tag_paths(tags)) STORED;
+
+CREATE INDEX idx_books_tag_paths ON books USING GIN(tag_paths);
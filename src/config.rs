@@ -1,5 +1,7 @@
 use std::env;
 
+use book_notes::{TagAliases, TagNormalizer};
+
 /// Application configuration loaded from environment variables
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -8,6 +10,8 @@ pub struct Config {
     pub server_port: u16,
     pub log_level: String,
     pub environment: Environment,
+    /// Tag aliases from `TAG_ALIASES`, e.g. `scifi=fiction/sci-fi,sf=fiction/sci-fi`
+    pub tag_aliases: TagAliases,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -39,12 +43,17 @@ impl Config {
             .unwrap_or_else(|_| "development".to_string())
             .parse()?;
 
+        let tag_aliases =
+            TagNormalizer::parse_aliases(&env::var("TAG_ALIASES").unwrap_or_default())
+                .map_err(ConfigError::InvalidTagAliases)?;
+
         Ok(Config {
             database_url,
             server_host,
             server_port,
             log_level,
             environment,
+            tag_aliases,
         })
     }
 
@@ -67,6 +76,8 @@ pub enum ConfigError {
     InvalidPort,
     #[error("Invalid environment: {0}")]
     InvalidEnvironment(String),
+    #[error("Invalid TAG_ALIASES: {0}")]
+    InvalidTagAliases(String),
 }
 
 impl std::str::FromStr for Environment {
@@ -0,0 +1,220 @@
+//! Markdown export with YAML front matter, laid out for an Obsidian vault
+
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Write};
+
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::models::{Book, BookStatus, Highlight, Note};
+
+/// Folder inside the archive that holds one file per book
+const BOOKS_DIR: &str = "Books";
+
+/// Bundle every book as a Markdown file inside a zip archive.
+///
+/// `notes` and `highlights` may cover any books; they are grouped per book and
+/// written in position order under the book's front matter.
+pub fn write_markdown_zip(
+    books: &[Book],
+    notes: &[Note],
+    highlights: &[Highlight],
+) -> Result<Vec<u8>, String> {
+    let mut notes_by_book: HashMap<i32, Vec<&Note>> = HashMap::new();
+    for note in notes {
+        notes_by_book.entry(note.book_id).or_default().push(note);
+    }
+
+    let mut highlights_by_book: HashMap<i32, Vec<&Highlight>> = HashMap::new();
+    for highlight in highlights {
+        highlights_by_book
+            .entry(highlight.book_id)
+            .or_default()
+            .push(highlight);
+    }
+    for highlights in highlights_by_book.values_mut() {
+        highlights.sort_by_key(|h| {
+            (
+                h.location_start.is_none(),
+                h.location_start,
+                h.page_number.is_none(),
+                h.page_number,
+            )
+        });
+    }
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    let mut used_names = HashSet::new();
+
+    for book in books {
+        let mut name = file_stem(book);
+        if !used_names.insert(name.clone()) {
+            name = format!("{} ({})", name, book.id);
+            used_names.insert(name.clone());
+        }
+
+        let markdown = render_book(
+            book,
+            notes_by_book.get(&book.id).map_or(&[], Vec::as_slice),
+            highlights_by_book.get(&book.id).map_or(&[], Vec::as_slice),
+        );
+
+        zip.start_file(format!("{}/{}.md", BOOKS_DIR, name), options)
+            .map_err(|e| format!("Failed to add {} to archive: {}", name, e))?;
+        zip.write_all(markdown.as_bytes())
+            .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+    }
+
+    let cursor = zip
+        .finish()
+        .map_err(|e| format!("Failed to finish archive: {}", e))?;
+    Ok(cursor.into_inner())
+}
+
+/// Render a single book as Markdown with YAML front matter
+pub fn render_book(book: &Book, notes: &[&Note], highlights: &[&Highlight]) -> String {
+    let mut out = String::new();
+
+    out.push_str("---\n");
+    out.push_str(&format!("title: {}\n", yaml_string(&book.title)));
+    out.push_str(&format!(
+        "author: {}\n",
+        yaml_string(&wiki_link(&book.author))
+    ));
+    out.push_str(&format!("status: {}\n", status_name(book.status)));
+    if let Some(rating) = book.rating {
+        out.push_str(&format!("rating: {}\n", rating));
+    }
+    if book.tags.is_empty() {
+        out.push_str("tags: []\n");
+    } else {
+        out.push_str("tags:\n");
+        for tag in &book.tags {
+            out.push_str(&format!("  - {}\n", yaml_string(&tag_name(tag))));
+        }
+    }
+    out.push_str(&format!("date_added: {}\n", book.date_added));
+    if let Some(date_started) = book.date_started {
+        out.push_str(&format!("date_started: {}\n", date_started));
+    }
+    if let Some(date_finished) = book.date_finished {
+        out.push_str(&format!("date_finished: {}\n", date_finished));
+    }
+    if let Some(cover_url) = &book.cover_url {
+        out.push_str(&format!("cover: {}\n", yaml_string(cover_url)));
+    }
+    if let Some(isbn) = book.isbn13.as_ref().or(book.isbn.as_ref()) {
+        out.push_str(&format!("isbn: {}\n", yaml_string(isbn)));
+    }
+    out.push_str("---\n\n");
+
+    out.push_str(&format!("# {}\n\n", book.title));
+    out.push_str(&format!("by {}\n", wiki_link(&book.author)));
+    if !book.tags.is_empty() {
+        let tags: Vec<String> = book
+            .tags
+            .iter()
+            .map(|tag| format!("#{}", tag_name(tag)))
+            .collect();
+        out.push_str(&format!("\n{}\n", tags.join(" ")));
+    }
+    if let Some(description) = &book.description {
+        out.push_str(&format!("\n{}\n", description.trim()));
+    }
+
+    if !highlights.is_empty() {
+        out.push_str("\n## Highlights\n");
+        for highlight in highlights {
+            out.push('\n');
+            for line in highlight.text.lines() {
+                out.push_str(&format!("> {}\n", line));
+            }
+            if let Some(position) = highlight_position(highlight) {
+                out.push_str(&format!("> — {}\n", position));
+            }
+        }
+    }
+
+    if !notes.is_empty() {
+        out.push_str("\n## Notes\n");
+        for note in notes {
+            out.push('\n');
+            out.push_str(note.content.trim());
+            out.push('\n');
+            if let Some(page) = note.page_number {
+                out.push_str(&format!("*p. {}*\n", page));
+            }
+        }
+    }
+
+    out
+}
+
+fn status_name(status: BookStatus) -> &'static str {
+    match status {
+        BookStatus::Reading => "reading",
+        BookStatus::Finished => "finished",
+        BookStatus::Wishlist => "wishlist",
+        BookStatus::Paused => "paused",
+        BookStatus::Abandoned => "abandoned",
+    }
+}
+
+/// `[[Author]]` link, with characters Obsidian does not allow in links removed
+fn wiki_link(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .filter(|c| !matches!(c, '[' | ']' | '|' | '#' | '^'))
+        .collect();
+    format!("[[{}]]", cleaned.trim())
+}
+
+/// Obsidian tags cannot contain spaces; nested tags keep their `/` separators
+fn tag_name(tag: &str) -> String {
+    tag.trim()
+        .trim_start_matches('#')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Human-readable position of a highlight, e.g. `p. 12, loc. 180-182`
+fn highlight_position(highlight: &Highlight) -> Option<String> {
+    let mut parts = Vec::new();
+    if let Some(chapter) = &highlight.chapter {
+        parts.push(chapter.clone());
+    }
+    if let Some(page) = highlight.page_number {
+        parts.push(format!("p. {}", page));
+    }
+    match (highlight.location_start, highlight.location_end) {
+        (Some(start), Some(end)) if end != start => parts.push(format!("loc. {}-{}", start, end)),
+        (Some(start), _) => parts.push(format!("loc. {}", start)),
+        _ => {}
+    }
+    if let Some(percent) = highlight.percent {
+        parts.push(format!("{:.0}%", percent));
+    }
+
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+/// Double-quoted YAML scalar
+fn yaml_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// `Title - Author`, stripped of characters that are invalid in file names
+fn file_stem(book: &Book) -> String {
+    let name = format!("{} - {}", book.title, book.author);
+    let cleaned: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    cleaned.trim().trim_end_matches('.').to_string()
+}
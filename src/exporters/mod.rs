@@ -0,0 +1,3 @@
+//! Writers for export formats that have no import counterpart
+
+pub mod markdown;
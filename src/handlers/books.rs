@@ -21,7 +21,7 @@ pub async fn get_book_by_id(
     State(app_state): State<AppState>,
     Path(id): Path<i32>,
 ) -> ApiResult<impl IntoResponse> {
-    let book = app_state.book_service.get_book_by_id(id).await?;
+    let book = app_state.progress_service.get_book_detail(id).await?;
     Ok(Json(book))
 }
 
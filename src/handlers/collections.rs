@@ -0,0 +1,113 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::errors::ApiResult;
+use crate::models::{
+    AddCollectionBookRequest, CreateCollectionRequest, ReorderRequest, UpdateCollectionRequest,
+};
+use crate::services::AppState;
+
+pub async fn get_collections(State(app_state): State<AppState>) -> ApiResult<impl IntoResponse> {
+    let collections = app_state.collection_service.get_all_collections().await?;
+    Ok(Json(collections))
+}
+
+pub async fn create_collection(
+    State(app_state): State<AppState>,
+    Json(request): Json<CreateCollectionRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let collection = app_state
+        .collection_service
+        .create_collection(request)
+        .await?;
+    Ok((StatusCode::CREATED, Json(collection)))
+}
+
+pub async fn reorder_collections(
+    State(app_state): State<AppState>,
+    Json(request): Json<ReorderRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let collections = app_state
+        .collection_service
+        .reorder_collections(request)
+        .await?;
+    Ok(Json(collections))
+}
+
+pub async fn get_collection_by_id(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<impl IntoResponse> {
+    let collection = app_state
+        .collection_service
+        .get_collection_by_id(id)
+        .await?;
+    Ok(Json(collection))
+}
+
+pub async fn update_collection(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(request): Json<UpdateCollectionRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let collection = app_state
+        .collection_service
+        .update_collection(id, request)
+        .await?;
+    Ok(Json(collection))
+}
+
+pub async fn delete_collection(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<impl IntoResponse> {
+    app_state.collection_service.delete_collection(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_collection_books(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<impl IntoResponse> {
+    let books = app_state
+        .collection_service
+        .get_collection_books(id)
+        .await?;
+    Ok(Json(books))
+}
+
+pub async fn add_collection_book(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(request): Json<AddCollectionBookRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let books = app_state.collection_service.add_book(id, request).await?;
+    Ok(Json(books))
+}
+
+pub async fn reorder_collection_books(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(request): Json<ReorderRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let books = app_state
+        .collection_service
+        .reorder_books(id, request)
+        .await?;
+    Ok(Json(books))
+}
+
+pub async fn remove_collection_book(
+    State(app_state): State<AppState>,
+    Path((id, book_id)): Path<(i32, i32)>,
+) -> ApiResult<impl IntoResponse> {
+    app_state
+        .collection_service
+        .remove_book(id, book_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,54 @@
+use axum::{
+    body::Body,
+    extract::State,
+    http::header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    response::IntoResponse,
+};
+use chrono::Utc;
+
+use crate::errors::ApiResult;
+use crate::services::AppState;
+
+pub async fn export_backup(State(app_state): State<AppState>) -> ApiResult<impl IntoResponse> {
+    let exported_at = Utc::now();
+    let filename = format!(
+        "attachment; filename=\"book-notes-backup-{}.json\"",
+        exported_at.format("%Y%m%d")
+    );
+    let body = Body::from_stream(app_state.export_service.stream_backup(exported_at));
+    Ok((
+        [
+            (CONTENT_TYPE, "application/json".to_string()),
+            (CONTENT_DISPOSITION, filename),
+        ],
+        body,
+    ))
+}
+
+pub async fn export_storygraph(State(app_state): State<AppState>) -> ApiResult<impl IntoResponse> {
+    let csv = app_state.export_service.export_storygraph_csv().await?;
+    Ok((
+        [
+            (CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (
+                CONTENT_DISPOSITION,
+                "attachment; filename=\"storygraph_export.csv\"",
+            ),
+        ],
+        csv,
+    ))
+}
+
+pub async fn export_markdown(State(app_state): State<AppState>) -> ApiResult<impl IntoResponse> {
+    let archive = app_state.export_service.export_markdown_zip().await?;
+    Ok((
+        [
+            (CONTENT_TYPE, "application/zip"),
+            (
+                CONTENT_DISPOSITION,
+                "attachment; filename=\"book-notes-markdown.zip\"",
+            ),
+        ],
+        archive,
+    ))
+}
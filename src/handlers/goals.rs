@@ -0,0 +1,51 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::errors::ApiResult;
+use crate::models::{CreateGoalRequest, GoalFilter, UpdateGoalRequest};
+use crate::services::AppState;
+
+pub async fn get_goals(
+    State(app_state): State<AppState>,
+    Query(filter): Query<GoalFilter>,
+) -> ApiResult<impl IntoResponse> {
+    let goals = app_state.goal_service.get_goals(filter).await?;
+    Ok(Json(goals))
+}
+
+pub async fn create_goal(
+    State(app_state): State<AppState>,
+    Json(request): Json<CreateGoalRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let goal = app_state.goal_service.create_goal(request).await?;
+    Ok((StatusCode::CREATED, Json(goal)))
+}
+
+pub async fn get_goal_by_id(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<impl IntoResponse> {
+    let goal = app_state.goal_service.get_goal_by_id(id).await?;
+    Ok(Json(goal))
+}
+
+pub async fn update_goal(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(request): Json<UpdateGoalRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let goal = app_state.goal_service.update_goal(id, request).await?;
+    Ok(Json(goal))
+}
+
+pub async fn delete_goal(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<impl IntoResponse> {
+    app_state.goal_service.delete_goal(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
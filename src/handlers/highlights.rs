@@ -0,0 +1,62 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::errors::ApiResult;
+use crate::models::{CreateHighlightRequest, HighlightFilter, UpdateHighlightRequest};
+use crate::services::AppState;
+
+pub async fn get_book_highlights(
+    State(app_state): State<AppState>,
+    Path(book_id): Path<i32>,
+    Query(filter): Query<HighlightFilter>,
+) -> ApiResult<impl IntoResponse> {
+    let highlights = app_state
+        .highlight_service
+        .get_highlights_for_book(book_id, filter.sort.unwrap_or_default())
+        .await?;
+    Ok(Json(highlights))
+}
+
+pub async fn create_highlight(
+    State(app_state): State<AppState>,
+    Path(book_id): Path<i32>,
+    Json(request): Json<CreateHighlightRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let highlight = app_state
+        .highlight_service
+        .create_highlight(book_id, request)
+        .await?;
+    Ok((StatusCode::CREATED, Json(highlight)))
+}
+
+pub async fn get_highlight_by_id(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<impl IntoResponse> {
+    let highlight = app_state.highlight_service.get_highlight_by_id(id).await?;
+    Ok(Json(highlight))
+}
+
+pub async fn update_highlight(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(request): Json<UpdateHighlightRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let highlight = app_state
+        .highlight_service
+        .update_highlight(id, request)
+        .await?;
+    Ok(Json(highlight))
+}
+
+pub async fn delete_highlight(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<impl IntoResponse> {
+    app_state.highlight_service.delete_highlight(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,78 @@
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+    Json,
+};
+
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{LibraryImportParams, RestoreParams};
+use crate::services::AppState;
+
+/// Accepts the raw contents of a Kindle `My Clippings.txt` file as the request body
+pub async fn import_kindle_clippings(
+    State(app_state): State<AppState>,
+    body: String,
+) -> ApiResult<impl IntoResponse> {
+    if body.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Request body must contain the clippings file".to_string(),
+        ));
+    }
+
+    let report = app_state
+        .import_service
+        .import_kindle_clippings(&body)
+        .await?;
+    Ok(Json(report))
+}
+
+/// Accepts a Goodreads library export CSV; previews the import unless `dry_run=false`
+pub async fn import_goodreads(
+    State(app_state): State<AppState>,
+    Query(params): Query<LibraryImportParams>,
+    body: String,
+) -> ApiResult<impl IntoResponse> {
+    if body.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Request body must contain the Goodreads CSV export".to_string(),
+        ));
+    }
+
+    let report = app_state
+        .import_service
+        .import_goodreads(&body, params.dry_run.unwrap_or(true))
+        .await?;
+    Ok(Json(report))
+}
+
+/// Accepts a StoryGraph library export CSV; previews the import unless `dry_run=false`
+pub async fn import_storygraph(
+    State(app_state): State<AppState>,
+    Query(params): Query<LibraryImportParams>,
+    body: String,
+) -> ApiResult<impl IntoResponse> {
+    if body.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Request body must contain the StoryGraph CSV export".to_string(),
+        ));
+    }
+
+    let report = app_state
+        .import_service
+        .import_storygraph(&body, params.dry_run.unwrap_or(true))
+        .await?;
+    Ok(Json(report))
+}
+
+/// Restores a JSON backup produced by `GET /api/export`
+pub async fn restore_backup(
+    State(app_state): State<AppState>,
+    Query(params): Query<RestoreParams>,
+    body: String,
+) -> ApiResult<impl IntoResponse> {
+    let report = app_state
+        .import_service
+        .restore_backup(&body, params.conflict.unwrap_or_default())
+        .await?;
+    Ok(Json(report))
+}
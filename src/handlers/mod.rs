@@ -1 +1,13 @@
 pub mod books;
+pub mod collections;
+pub mod exports;
+pub mod goals;
+pub mod highlights;
+pub mod imports;
+pub mod notes;
+pub mod progress;
+pub mod read_throughs;
+pub mod reading_sessions;
+pub mod search;
+pub mod stats;
+pub mod tags;
@@ -0,0 +1,52 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::errors::ApiResult;
+use crate::models::{CreateNoteRequest, UpdateNoteRequest};
+use crate::services::AppState;
+
+pub async fn get_book_notes(
+    State(app_state): State<AppState>,
+    Path(book_id): Path<i32>,
+) -> ApiResult<impl IntoResponse> {
+    let notes = app_state.note_service.get_notes_for_book(book_id).await?;
+    Ok(Json(notes))
+}
+
+pub async fn create_note(
+    State(app_state): State<AppState>,
+    Path(book_id): Path<i32>,
+    Json(request): Json<CreateNoteRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let note = app_state.note_service.create_note(book_id, request).await?;
+    Ok((StatusCode::CREATED, Json(note)))
+}
+
+pub async fn get_note_by_id(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<impl IntoResponse> {
+    let note = app_state.note_service.get_note_by_id(id).await?;
+    Ok(Json(note))
+}
+
+pub async fn update_note(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(request): Json<UpdateNoteRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let note = app_state.note_service.update_note(id, request).await?;
+    Ok(Json(note))
+}
+
+pub async fn delete_note(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<impl IntoResponse> {
+    app_state.note_service.delete_note(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,33 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::errors::ApiResult;
+use crate::models::RecordProgressRequest;
+use crate::services::AppState;
+
+pub async fn get_book_progress(
+    State(app_state): State<AppState>,
+    Path(book_id): Path<i32>,
+) -> ApiResult<impl IntoResponse> {
+    let progress = app_state
+        .progress_service
+        .get_progress_for_book(book_id)
+        .await?;
+    Ok(Json(progress))
+}
+
+pub async fn record_progress(
+    State(app_state): State<AppState>,
+    Path(book_id): Path<i32>,
+    Json(request): Json<RecordProgressRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let progress = app_state
+        .progress_service
+        .record_progress(book_id, request)
+        .await?;
+    Ok((StatusCode::CREATED, Json(progress)))
+}
@@ -0,0 +1,67 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::errors::ApiResult;
+use crate::models::{CreateReadThroughRequest, UpdateReadThroughRequest};
+use crate::services::AppState;
+
+pub async fn get_book_read_throughs(
+    State(app_state): State<AppState>,
+    Path(book_id): Path<i32>,
+) -> ApiResult<impl IntoResponse> {
+    let read_throughs = app_state
+        .read_through_service
+        .get_read_throughs_for_book(book_id)
+        .await?;
+    Ok(Json(read_throughs))
+}
+
+pub async fn create_read_through(
+    State(app_state): State<AppState>,
+    Path(book_id): Path<i32>,
+    Json(request): Json<CreateReadThroughRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let read_through = app_state
+        .read_through_service
+        .create_read_through(book_id, request)
+        .await?;
+    Ok((StatusCode::CREATED, Json(read_through)))
+}
+
+pub async fn get_read_through_by_id(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<impl IntoResponse> {
+    let read_through = app_state
+        .read_through_service
+        .get_read_through_by_id(id)
+        .await?;
+    Ok(Json(read_through))
+}
+
+pub async fn update_read_through(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(request): Json<UpdateReadThroughRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let read_through = app_state
+        .read_through_service
+        .update_read_through(id, request)
+        .await?;
+    Ok(Json(read_through))
+}
+
+pub async fn delete_read_through(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<impl IntoResponse> {
+    app_state
+        .read_through_service
+        .delete_read_through(id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,88 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::errors::ApiResult;
+use crate::models::{
+    CreateReadingSessionRequest, ReadingSessionFilter, UpdateReadingSessionRequest,
+};
+use crate::services::AppState;
+
+pub async fn get_book_sessions(
+    State(app_state): State<AppState>,
+    Path(book_id): Path<i32>,
+) -> ApiResult<impl IntoResponse> {
+    let sessions = app_state
+        .reading_session_service
+        .get_sessions_for_book(book_id)
+        .await?;
+    Ok(Json(sessions))
+}
+
+pub async fn create_session(
+    State(app_state): State<AppState>,
+    Path(book_id): Path<i32>,
+    Json(request): Json<CreateReadingSessionRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let session = app_state
+        .reading_session_service
+        .create_session(book_id, request)
+        .await?;
+    Ok((StatusCode::CREATED, Json(session)))
+}
+
+pub async fn get_sessions(
+    State(app_state): State<AppState>,
+    Query(filter): Query<ReadingSessionFilter>,
+) -> ApiResult<impl IntoResponse> {
+    let sessions = app_state
+        .reading_session_service
+        .get_sessions(filter)
+        .await?;
+    Ok(Json(sessions))
+}
+
+pub async fn get_session_summary(
+    State(app_state): State<AppState>,
+    Query(filter): Query<ReadingSessionFilter>,
+) -> ApiResult<impl IntoResponse> {
+    let summary = app_state
+        .reading_session_service
+        .get_summary(filter)
+        .await?;
+    Ok(Json(summary))
+}
+
+pub async fn get_session_by_id(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<impl IntoResponse> {
+    let session = app_state
+        .reading_session_service
+        .get_session_by_id(id)
+        .await?;
+    Ok(Json(session))
+}
+
+pub async fn update_session(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(request): Json<UpdateReadingSessionRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let session = app_state
+        .reading_session_service
+        .update_session(id, request)
+        .await?;
+    Ok(Json(session))
+}
+
+pub async fn delete_session(
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<impl IntoResponse> {
+    app_state.reading_session_service.delete_session(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,20 @@
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+    Json,
+};
+
+use crate::errors::ApiResult;
+use crate::models::SearchParams;
+use crate::services::AppState;
+
+pub async fn search(
+    State(app_state): State<AppState>,
+    Query(params): Query<SearchParams>,
+) -> ApiResult<impl IntoResponse> {
+    let results = app_state
+        .search_service
+        .search(&params.q, params.limit)
+        .await?;
+    Ok(Json(results))
+}
@@ -0,0 +1,17 @@
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+    Json,
+};
+
+use crate::errors::ApiResult;
+use crate::models::StatsFilter;
+use crate::services::AppState;
+
+pub async fn get_stats(
+    State(app_state): State<AppState>,
+    Query(filter): Query<StatsFilter>,
+) -> ApiResult<impl IntoResponse> {
+    let stats = app_state.stats_service.get_stats(filter).await?;
+    Ok(Json(stats))
+}
@@ -0,0 +1,39 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    Json,
+};
+
+use crate::errors::ApiResult;
+use crate::models::{MergeTagsRequest, RenameTagRequest};
+use crate::services::AppState;
+
+pub async fn get_tags(State(app_state): State<AppState>) -> ApiResult<impl IntoResponse> {
+    let tags = app_state.tag_service.list_tags().await?;
+    Ok(Json(tags))
+}
+
+pub async fn rename_tag(
+    State(app_state): State<AppState>,
+    Path(tag): Path<String>,
+    Json(request): Json<RenameTagRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let report = app_state.tag_service.rename_tag(&tag, request).await?;
+    Ok(Json(report))
+}
+
+pub async fn merge_tags(
+    State(app_state): State<AppState>,
+    Json(request): Json<MergeTagsRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let report = app_state.tag_service.merge_tags(request).await?;
+    Ok(Json(report))
+}
+
+pub async fn delete_tag(
+    State(app_state): State<AppState>,
+    Path(tag): Path<String>,
+) -> ApiResult<impl IntoResponse> {
+    let report = app_state.tag_service.delete_tag(&tag).await?;
+    Ok(Json(report))
+}
@@ -0,0 +1,193 @@
+//! Versioned JSON backup format
+
+use serde_json::{json, Value};
+
+use crate::models::Backup;
+
+/// Identifies a document as a library backup
+pub const BACKUP_FORMAT: &str = "book-notes-backup";
+
+/// Current backup schema version; bump it and add an upgrade step when the
+/// shape of [`Backup`] changes
+pub const BACKUP_VERSION: u32 = 2;
+
+/// Parse a backup document, upgrading older versions to the current schema.
+pub fn parse_backup(input: &str) -> Result<Backup, String> {
+    let document: Value =
+        serde_json::from_str(input).map_err(|e| format!("Invalid backup JSON: {}", e))?;
+
+    if document.get("format").and_then(Value::as_str) != Some(BACKUP_FORMAT) {
+        return Err(format!("Not a {} document", BACKUP_FORMAT));
+    }
+
+    let version = document
+        .get("version")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| "Backup is missing a version".to_string())?;
+    let version =
+        u32::try_from(version).map_err(|_| format!("Unsupported backup version {}", version))?;
+
+    if version == 0 || version > BACKUP_VERSION {
+        return Err(format!(
+            "Unsupported backup version {} (this server supports up to {})",
+            version, BACKUP_VERSION
+        ));
+    }
+
+    let document = upgrade(document, version)?;
+    serde_json::from_value(document).map_err(|e| format!("Invalid backup contents: {}", e))
+}
+
+/// Apply upgrade steps one version at a time until the document is current.
+fn upgrade(mut document: Value, mut version: u32) -> Result<Value, String> {
+    while version < BACKUP_VERSION {
+        document = match version {
+            1 => upgrade_v1_to_v2(document)?,
+            other => return Err(format!("No upgrade path from backup version {}", other)),
+        };
+        version += 1;
+    }
+    Ok(document)
+}
+
+/// Version 2 added read-throughs. Version 1 backups get the read-throughs a
+/// book's status and dates imply: one finished on `date_finished`, and one in
+/// progress since `date_started` for books being read, paused or abandoned.
+fn upgrade_v1_to_v2(mut document: Value) -> Result<Value, String> {
+    let exported_at = document.get("exported_at").cloned().unwrap_or(Value::Null);
+    let books = document
+        .get("books")
+        .and_then(Value::as_array)
+        .ok_or_else(|| "Backup is missing its books".to_string())?;
+
+    let mut read_throughs = Vec::new();
+    for book in books {
+        let status = book.get("status").and_then(Value::as_str);
+        let date_finished = book.get("date_finished").filter(|date| !date.is_null());
+        let date_started = book.get("date_started").cloned().unwrap_or(Value::Null);
+
+        if let Some(date_finished) = date_finished {
+            let started_on = match status {
+                Some("finished") => date_started.clone(),
+                _ => Value::Null,
+            };
+            read_throughs.push(json!({
+                "id": read_throughs.len() + 1,
+                "book_id": book.get("id"),
+                "started_on": started_on,
+                "finished_on": date_finished,
+                "rating": book.get("rating"),
+                "format": null,
+                "created_at": exported_at,
+                "updated_at": exported_at,
+            }));
+        }
+        if matches!(status, Some("reading" | "paused" | "abandoned")) {
+            read_throughs.push(json!({
+                "id": read_throughs.len() + 1,
+                "book_id": book.get("id"),
+                "started_on": date_started,
+                "finished_on": null,
+                "rating": null,
+                "format": null,
+                "created_at": exported_at,
+                "updated_at": exported_at,
+            }));
+        }
+    }
+
+    document["read_throughs"] = Value::Array(read_throughs);
+    Ok(document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn book(id: i32, status: &str, date_started: Value, date_finished: Value) -> Value {
+        json!({
+            "id": id,
+            "title": format!("Book {}", id),
+            "author": "Author",
+            "tags": [],
+            "status": status,
+            "date_added": "2024-01-01",
+            "date_started": date_started,
+            "date_finished": date_finished,
+            "rating": 4,
+            "notes_count": 0,
+        })
+    }
+
+    fn document(version: u32, books: Vec<Value>) -> String {
+        json!({
+            "format": BACKUP_FORMAT,
+            "version": version,
+            "exported_at": "2024-06-01T12:00:00Z",
+            "tags": [],
+            "books": books,
+            "notes": [],
+            "highlights": [],
+        })
+        .to_string()
+    }
+
+    fn date(value: &str) -> Option<NaiveDate> {
+        Some(value.parse().unwrap())
+    }
+
+    #[test]
+    fn upgrades_v1_books_into_read_throughs() {
+        let input = document(
+            1,
+            vec![
+                book(1, "finished", json!("2024-02-01"), json!("2024-03-01")),
+                book(2, "reading", json!("2024-04-01"), json!("2023-12-01")),
+                book(3, "paused", json!("2024-05-01"), Value::Null),
+                book(4, "wishlist", Value::Null, Value::Null),
+            ],
+        );
+
+        let backup = parse_backup(&input).unwrap();
+        let read_throughs: Vec<_> = backup
+            .read_throughs
+            .iter()
+            .map(|r| (r.book_id, r.started_on, r.finished_on, r.rating))
+            .collect();
+
+        assert_eq!(backup.version, 1);
+        assert_eq!(
+            read_throughs,
+            vec![
+                (1, date("2024-02-01"), date("2024-03-01"), Some(4)),
+                (2, None, date("2023-12-01"), Some(4)),
+                (2, date("2024-04-01"), None, None),
+                (3, date("2024-05-01"), None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn current_version_requires_read_throughs() {
+        let input = document(BACKUP_VERSION, vec![]);
+        assert!(parse_backup(&input)
+            .unwrap_err()
+            .contains("missing field `read_throughs`"));
+
+        let mut current: Value = serde_json::from_str(&input).unwrap();
+        current["read_throughs"] = json!([]);
+        let backup = parse_backup(&current.to_string()).unwrap();
+        assert_eq!(backup.version, BACKUP_VERSION);
+        assert!(backup.read_throughs.is_empty());
+    }
+
+    #[test]
+    fn rejects_unknown_formats_and_versions() {
+        assert!(parse_backup("{}").unwrap_err().contains("Not a"));
+        assert!(parse_backup(&document(0, vec![])).is_err());
+        assert!(parse_backup(&document(BACKUP_VERSION + 1, vec![]))
+            .unwrap_err()
+            .contains("Unsupported backup version"));
+    }
+}
@@ -0,0 +1,187 @@
+use serde::Deserialize;
+
+use super::{clean_isbn, parse_export_date, LibraryEntry, LibraryRow, MAX_TAGS};
+use crate::models::BookStatus;
+
+/// Exclusive shelves that map onto `BookStatus` rather than tags
+const STATUS_SHELVES: [&str; 3] = ["read", "currently-reading", "to-read"];
+
+/// Columns of the Goodreads library export that we carry over
+#[derive(Debug, Deserialize)]
+struct GoodreadsRecord {
+    #[serde(rename = "Title", default)]
+    title: String,
+    #[serde(rename = "Author", default)]
+    author: String,
+    #[serde(rename = "ISBN", default)]
+    isbn: String,
+    #[serde(rename = "ISBN13", default)]
+    isbn13: String,
+    #[serde(rename = "My Rating", default)]
+    my_rating: String,
+    #[serde(rename = "Date Read", default)]
+    date_read: String,
+    #[serde(rename = "Date Added", default)]
+    date_added: String,
+    #[serde(rename = "Bookshelves", default)]
+    bookshelves: String,
+    #[serde(rename = "Exclusive Shelf", default)]
+    exclusive_shelf: String,
+}
+
+/// Parse a Goodreads library export (`goodreads_library_export.csv`).
+///
+/// Rows that fail to parse are returned with an error instead of aborting the
+/// whole file, so the preview can point at the offending line.
+pub fn parse_goodreads_csv(input: &str) -> Result<Vec<LibraryRow>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(input.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Invalid CSV header: {}", e))?;
+    if !headers.iter().any(|h| h == "Title") || !headers.iter().any(|h| h == "Author") {
+        return Err("Not a Goodreads export: missing Title or Author column".to_string());
+    }
+
+    let rows = reader
+        .deserialize::<GoodreadsRecord>()
+        .enumerate()
+        .map(|(index, record)| LibraryRow {
+            row: index + 1,
+            entry: record
+                .map_err(|e| format!("Invalid row: {}", e))
+                .and_then(to_library_entry),
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+fn to_library_entry(record: GoodreadsRecord) -> Result<LibraryEntry, String> {
+    if record.title.is_empty() {
+        return Err("Missing title".to_string());
+    }
+    if record.author.is_empty() {
+        return Err("Missing author".to_string());
+    }
+
+    // Goodreads uses 0 for "not rated"
+    let rating = match record.my_rating.as_str() {
+        "" | "0" => None,
+        value => Some(
+            value
+                .parse::<i32>()
+                .ok()
+                .filter(|rating| (1..=5).contains(rating))
+                .ok_or_else(|| format!("Invalid rating: {}", value))?,
+        ),
+    };
+
+    let status = match record.exclusive_shelf.as_str() {
+        "read" => Some(BookStatus::Finished),
+        "currently-reading" => Some(BookStatus::Reading),
+        "to-read" => Some(BookStatus::Wishlist),
+        _ => None,
+    };
+
+    let mut tags: Vec<String> = Vec::new();
+    let shelves = record
+        .bookshelves
+        .split(',')
+        .chain(std::iter::once(record.exclusive_shelf.as_str()));
+    for shelf in shelves.map(str::trim) {
+        if !shelf.is_empty() && !STATUS_SHELVES.contains(&shelf) && !tags.iter().any(|t| t == shelf)
+        {
+            tags.push(shelf.to_string());
+        }
+    }
+    tags.truncate(MAX_TAGS);
+
+    Ok(LibraryEntry {
+        title: record.title,
+        author: record.author,
+        isbn: clean_isbn(&record.isbn),
+        isbn13: clean_isbn(&record.isbn13),
+        status,
+        rating,
+        date_added: parse_export_date(&record.date_added),
+        date_finished: parse_export_date(&record.date_read),
+        tags,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    const HEADER: &str = "Book Id,Title,Author,ISBN,ISBN13,My Rating,Date Read,Date Added,Bookshelves,Exclusive Shelf";
+
+    fn parse_row(row: &str) -> Result<LibraryEntry, String> {
+        let input = format!("{}\n{}\n", HEADER, row);
+        let mut rows = parse_goodreads_csv(&input).unwrap();
+        assert_eq!(rows.len(), 1);
+        rows.remove(0).entry
+    }
+
+    #[test]
+    fn maps_exclusive_shelves_to_status() {
+        let cases = [
+            ("read", Some(BookStatus::Finished)),
+            ("currently-reading", Some(BookStatus::Reading)),
+            ("to-read", Some(BookStatus::Wishlist)),
+            ("on-hold", None),
+        ];
+        for (shelf, status) in cases {
+            let entry = parse_row(&format!("1,Dune,Frank Herbert,,,0,,,,{}", shelf)).unwrap();
+            assert_eq!(entry.status, status, "shelf {}", shelf);
+        }
+    }
+
+    #[test]
+    fn unquotes_spreadsheet_isbns_and_parses_dates() {
+        let entry = parse_row(
+            r#"1,Dune,Frank Herbert,"=""0441172717""","=""9780441172719""",4,2024/05/01,2023/12/24,,read"#,
+        )
+        .unwrap();
+
+        assert_eq!(entry.isbn.as_deref(), Some("0441172717"));
+        assert_eq!(entry.isbn13.as_deref(), Some("9780441172719"));
+        assert_eq!(entry.rating, Some(4));
+        assert_eq!(entry.date_finished, NaiveDate::from_ymd_opt(2024, 5, 1));
+        assert_eq!(entry.date_added, NaiveDate::from_ymd_opt(2023, 12, 24));
+
+        let empty = parse_row(r#"2,Emma,Jane Austen,"=""""","=""""",0,,,,to-read"#).unwrap();
+        assert_eq!((empty.isbn, empty.isbn13, empty.rating), (None, None, None));
+        assert_eq!(empty.date_finished, None);
+    }
+
+    #[test]
+    fn turns_custom_shelves_into_tags() {
+        let entry =
+            parse_row(r#"1,Dune,Frank Herbert,,,0,,,"sci-fi, favorites, read, sci-fi",read"#)
+                .unwrap();
+        assert_eq!(entry.tags, vec!["sci-fi", "favorites"]);
+
+        let custom = parse_row("1,Dune,Frank Herbert,,,0,,,,owned").unwrap();
+        assert_eq!(custom.tags, vec!["owned"]);
+
+        let shelves: Vec<String> = (0..30).map(|i| format!("shelf-{}", i)).collect();
+        let many = parse_row(&format!(
+            "1,Dune,Frank Herbert,,,0,,,\"{}\",read",
+            shelves.join(", ")
+        ))
+        .unwrap();
+        assert_eq!(many.tags.len(), MAX_TAGS);
+    }
+
+    #[test]
+    fn reports_invalid_rows_and_files() {
+        assert!(parse_row("1,,Frank Herbert,,,0,,,,read").is_err());
+        assert!(parse_row("1,Dune,Frank Herbert,,,9,,,,read").is_err());
+        assert!(parse_goodreads_csv("Name,Writer\nDune,Herbert\n").is_err());
+    }
+}
@@ -0,0 +1,358 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+/// Separator line between entries in `My Clippings.txt`
+const ENTRY_SEPARATOR: &str = "==========";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClippingKind {
+    Highlight,
+    Note,
+    Bookmark,
+}
+
+/// A single entry from a Kindle `My Clippings.txt` file
+#[derive(Debug, Clone)]
+pub struct Clipping {
+    pub title: String,
+    pub author: Option<String>,
+    pub kind: ClippingKind,
+    pub page: Option<i32>,
+    pub location_start: Option<i32>,
+    pub location_end: Option<i32>,
+    pub added_at: Option<DateTime<Utc>>,
+    pub content: String,
+}
+
+/// Book title and optional author from the first line of an entry
+type TitleAndAuthor = (String, Option<String>);
+
+/// Start and optional end of a page or location range
+type Range = (Option<i32>, Option<i32>);
+
+/// Result of parsing a clippings file
+#[derive(Debug, Default)]
+pub struct ParsedClippings {
+    pub clippings: Vec<Clipping>,
+    /// Entries that could not be parsed and were dropped
+    pub malformed: usize,
+}
+
+/// Parse the contents of a Kindle `My Clippings.txt` file.
+///
+/// Each entry looks like:
+///
+/// ```text
+/// Crime and Punishment (Dostoevsky, Fyodor)
+/// - Your Highlight on page 12 | Location 180-182 | Added on Monday, January 1, 2024 10:00:00 AM
+///
+/// Pain and suffering are always inevitable for a large intelligence.
+/// ==========
+/// ```
+pub fn parse_clippings(input: &str) -> ParsedClippings {
+    let mut parsed = ParsedClippings::default();
+
+    for entry in input.split(ENTRY_SEPARATOR) {
+        if entry.trim().is_empty() {
+            continue;
+        }
+
+        match parse_entry(entry) {
+            Some(clipping) => parsed.clippings.push(clipping),
+            None => parsed.malformed += 1,
+        }
+    }
+
+    parsed
+}
+
+fn parse_entry(entry: &str) -> Option<Clipping> {
+    let mut lines = entry
+        .lines()
+        .map(|line| line.trim_start_matches('\u{feff}').trim_end_matches('\r'))
+        .skip_while(|line| line.trim().is_empty());
+
+    let (title, author) = parse_title_line(lines.next()?)?;
+    let metadata = lines.next()?.trim().trim_start_matches('-').trim();
+
+    let content = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+
+    let mut clipping = Clipping {
+        title,
+        author,
+        kind: parse_kind(metadata)?,
+        page: None,
+        location_start: None,
+        location_end: None,
+        added_at: None,
+        content,
+    };
+
+    for segment in metadata.split('|') {
+        let segment = segment.trim();
+        let lower = segment.to_lowercase();
+
+        if lower.starts_with("added on") {
+            clipping.added_at = segment.get("added on".len()..).and_then(parse_added_on);
+        } else if let Some(position) = lower.find("location").or_else(|| lower.find("loc.")) {
+            let (start, end) = parse_range(&lower[position..]);
+            clipping.location_start = start;
+            clipping.location_end = end;
+        } else if let Some(position) = lower.find("page") {
+            clipping.page = parse_range(&lower[position..]).0;
+        }
+    }
+
+    // Only bookmarks are allowed to have an empty body
+    if clipping.kind != ClippingKind::Bookmark && clipping.content.is_empty() {
+        return None;
+    }
+
+    Some(clipping)
+}
+
+/// Split `Title (Author)` into its parts, honoring parentheses inside the title
+fn parse_title_line(line: &str) -> Option<TitleAndAuthor> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+
+    if let Some(without_paren) = line.strip_suffix(')') {
+        let mut depth = 0;
+        for (index, ch) in without_paren.char_indices().rev() {
+            match ch {
+                ')' => depth += 1,
+                '(' if depth == 0 => {
+                    let title = without_paren[..index].trim();
+                    let author = without_paren[index + 1..].trim();
+                    if title.is_empty() {
+                        break;
+                    }
+                    let author = (!author.is_empty()).then(|| normalize_author(author));
+                    return Some((title.to_string(), author));
+                }
+                '(' => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+
+    Some((line.to_string(), None))
+}
+
+/// Kindle stores single authors as `Last, First`; flip them to `First Last`
+fn normalize_author(author: &str) -> String {
+    match author.split_once(',') {
+        Some((last, first)) if !first.contains(',') && !author.contains(';') => {
+            format!("{} {}", first.trim(), last.trim())
+        }
+        _ => author.to_string(),
+    }
+}
+
+fn parse_kind(metadata: &str) -> Option<ClippingKind> {
+    let first_segment = metadata.split('|').next()?.to_lowercase();
+
+    if first_segment.contains("highlight") {
+        Some(ClippingKind::Highlight)
+    } else if first_segment.contains("note") {
+        Some(ClippingKind::Note)
+    } else if first_segment.contains("bookmark") {
+        Some(ClippingKind::Bookmark)
+    } else {
+        None
+    }
+}
+
+/// Extract a `123` or `123-130` range, expanding abbreviated ends like `1234-36`
+fn parse_range(text: &str) -> Range {
+    let mut numbers = text
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|part| !part.is_empty());
+
+    let Some(start_digits) = numbers.next() else {
+        return (None, None);
+    };
+    let start = start_digits.parse::<i32>().ok();
+
+    let end = numbers.next().and_then(|end_digits| {
+        let end = end_digits.parse::<i32>().ok()?;
+        let start = start?;
+        if end >= start {
+            return Some(end);
+        }
+        // Old Kindles abbreviate the end of a range, e.g. "1234-36" means 1234-1236
+        let prefix_len = start_digits.len().checked_sub(end_digits.len())?;
+        format!("{}{}", &start_digits[..prefix_len], end_digits)
+            .parse::<i32>()
+            .ok()
+            .filter(|expanded| *expanded >= start)
+    });
+
+    (start, end)
+}
+
+/// Shortest title prefix accepted when Kindle cut a title mid-word
+const MIN_TRUNCATED_TITLE_LEN: usize = 20;
+
+/// Whether a clipping title names the same book as `book_title`, allowing for
+/// Kindle truncating long titles. Both titles must already be normalized.
+///
+/// One title has to be a prefix of the other, ending at a word boundary unless
+/// the shorter one is long enough that a mid-word cut is unambiguous.
+pub(crate) fn titles_match(clipping_title: &str, book_title: &str) -> bool {
+    let clipping_title = clipping_title
+        .trim_end_matches("...")
+        .trim_end_matches('\u{2026}')
+        .trim_end();
+    let (shorter, longer) = if clipping_title.len() <= book_title.len() {
+        (clipping_title, book_title)
+    } else {
+        (book_title, clipping_title)
+    };
+
+    if shorter.is_empty() || !longer.starts_with(shorter) {
+        return false;
+    }
+
+    match longer[shorter.len()..].chars().next() {
+        None => true,
+        Some(next) => !next.is_alphanumeric() || shorter.chars().count() >= MIN_TRUNCATED_TITLE_LEN,
+    }
+}
+
+/// Parse the `Added on Monday, January 1, 2024 10:00:00 AM` segment
+fn parse_added_on(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+
+    // Drop the weekday, which chrono cannot validate against localized names
+    let value = value.split_once(", ").map_or(value, |(_, rest)| rest);
+
+    [
+        "%B %d, %Y %I:%M:%S %p",
+        "%d %B %Y %H:%M:%S",
+        "%B %d, %Y %H:%M:%S",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .map(|naive| naive.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_highlight_with_page_and_location() {
+        let input = "Crime and Punishment (Dostoevsky, Fyodor)\n\
+            - Your Highlight on page 12 | Location 180-182 | Added on Monday, January 1, 2024 10:00:00 AM\n\
+            \n\
+            Pain and suffering are always inevitable.\n\
+            ==========\n";
+        let parsed = parse_clippings(input);
+
+        assert_eq!(parsed.malformed, 0);
+        let clipping = &parsed.clippings[0];
+        assert_eq!(clipping.title, "Crime and Punishment");
+        assert_eq!(clipping.author.as_deref(), Some("Fyodor Dostoevsky"));
+        assert_eq!(clipping.kind, ClippingKind::Highlight);
+        assert_eq!(clipping.page, Some(12));
+        assert_eq!(
+            (clipping.location_start, clipping.location_end),
+            (Some(180), Some(182))
+        );
+        assert_eq!(
+            clipping.added_at.map(|at| at.to_rfc3339()),
+            Some("2024-01-01T10:00:00+00:00".to_string())
+        );
+        assert_eq!(
+            clipping.content,
+            "Pain and suffering are always inevitable."
+        );
+    }
+
+    #[test]
+    fn parses_notes_bookmarks_and_page_only_metadata() {
+        let input = "Dune (Frank Herbert)\n\
+            - Your Note on page 7 | Added on Tuesday, January 2, 2024 09:30:00 PM\n\
+            \n\
+            Fear is the mind-killer\n\
+            ==========\n\
+            Dune (Frank Herbert)\n\
+            - Your Bookmark on Location 1234-36 | Added on Tuesday, January 2, 2024 09:31:00 PM\n\
+            \n\
+            \n\
+            ==========\n";
+        let parsed = parse_clippings(input);
+
+        assert_eq!(parsed.clippings.len(), 2);
+        let note = &parsed.clippings[0];
+        assert_eq!(note.kind, ClippingKind::Note);
+        assert_eq!(note.page, Some(7));
+        assert_eq!(note.location_start, None);
+
+        let bookmark = &parsed.clippings[1];
+        assert_eq!(bookmark.kind, ClippingKind::Bookmark);
+        assert_eq!(bookmark.page, None);
+        assert_eq!(
+            (bookmark.location_start, bookmark.location_end),
+            (Some(1234), Some(1236))
+        );
+        assert!(bookmark.content.is_empty());
+    }
+
+    #[test]
+    fn handles_bom_and_crlf_line_endings() {
+        let input = "\u{feff}Emma (Austen, Jane)\r\n\
+            - Your Highlight on Location 10-11 | Added on Monday, January 1, 2024 10:00:00 AM\r\n\
+            \r\n\
+            Handsome, clever, and rich\r\n\
+            ==========\r\n";
+        let parsed = parse_clippings(input);
+
+        assert_eq!(parsed.malformed, 0);
+        assert_eq!(parsed.clippings[0].title, "Emma");
+        assert_eq!(parsed.clippings[0].author.as_deref(), Some("Jane Austen"));
+        assert_eq!(parsed.clippings[0].content, "Handsome, clever, and rich");
+    }
+
+    #[test]
+    fn counts_malformed_entries() {
+        let input = "Title only\n==========\n\
+            Dune (Frank Herbert)\n- Your Highlight on page 3\n\n\n==========\n";
+        let parsed = parse_clippings(input);
+
+        assert!(parsed.clippings.is_empty());
+        assert_eq!(parsed.malformed, 2);
+    }
+
+    #[test]
+    fn keeps_parentheses_inside_titles() {
+        assert_eq!(
+            parse_title_line("The Hobbit (Illustrated) (Tolkien, J. R. R.)"),
+            Some((
+                "The Hobbit (Illustrated)".to_string(),
+                Some("J. R. R. Tolkien".to_string())
+            ))
+        );
+        assert_eq!(
+            parse_title_line("Untitled"),
+            Some(("Untitled".to_string(), None))
+        );
+    }
+
+    #[test]
+    fn matches_truncated_titles() {
+        assert!(titles_match("dune", "dune"));
+        assert!(titles_match(
+            "thinking, fast and slow",
+            "thinking, fast and slow: a very long subtitle"
+        ));
+        assert!(titles_match(
+            "the extraordinarily long title that kin...",
+            "the extraordinarily long title that kindle cut"
+        ));
+        assert!(!titles_match("dune", "dunes of arrakis"));
+        assert!(!titles_match("", "dune"));
+    }
+}
@@ -0,0 +1,57 @@
+//! Parsers and writers for third-party library and clippings formats
+
+pub mod backup;
+pub mod goodreads;
+pub mod kindle;
+pub mod storygraph;
+
+use chrono::NaiveDate;
+
+use crate::models::BookStatus;
+
+/// Maximum number of tags allowed on a book
+pub(crate) const MAX_TAGS: usize = 20;
+
+/// A book as described by a library export, independent of the source format
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LibraryEntry {
+    pub title: String,
+    pub author: String,
+    pub isbn: Option<String>,
+    pub isbn13: Option<String>,
+    pub status: Option<BookStatus>,
+    pub rating: Option<i32>,
+    pub date_added: Option<NaiveDate>,
+    pub date_finished: Option<NaiveDate>,
+    pub tags: Vec<String>,
+}
+
+/// One data row of a library export, numbered from 1 as spreadsheets show it
+#[derive(Debug)]
+pub struct LibraryRow {
+    pub row: usize,
+    pub entry: Result<LibraryEntry, String>,
+}
+
+/// Keep only the digits (and a trailing `X` check digit) of an ISBN cell
+pub(crate) fn clean_isbn(value: &str) -> Option<String> {
+    let cleaned: String = value
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == 'X' || *c == 'x')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    (!cleaned.is_empty()).then_some(cleaned)
+}
+
+/// Parse a date in any of the formats used by common exports
+pub(crate) fn parse_export_date(value: &str) -> Option<NaiveDate> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    ["%Y/%m/%d", "%Y-%m-%d", "%Y/%m/%d %H:%M:%S", "%m/%d/%Y"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+}
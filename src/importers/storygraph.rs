@@ -0,0 +1,377 @@
+//! StoryGraph library CSV format, used for both import and export
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use super::{clean_isbn, parse_export_date, LibraryEntry, LibraryRow, MAX_TAGS};
+use crate::models::{Book, BookStatus, ReadThrough};
+
+/// Column layout of the StoryGraph library export
+const HEADERS: [&str; 23] = [
+    "Title",
+    "Authors",
+    "Contributors",
+    "ISBN/UID",
+    "Format",
+    "Read Status",
+    "Date Added",
+    "Last Date Read",
+    "Dates Read",
+    "Read Count",
+    "Moods",
+    "Pace",
+    "Character- or Plot-Driven?",
+    "Strong Character Development?",
+    "Loveable Characters?",
+    "Diverse Characters?",
+    "Flawed Characters?",
+    "Star Rating",
+    "Review",
+    "Content Warnings",
+    "Content Warning Description",
+    "Tags",
+    "Owned?",
+];
+
+/// Moods are stored as tags under this prefix so they survive a round trip
+const MOOD_TAG_PREFIX: &str = "mood/";
+
+/// StoryGraph dates are written as `2024/01/31`
+const DATE_FORMAT: &str = "%Y/%m/%d";
+
+#[derive(Debug, Deserialize)]
+struct StoryGraphRecord {
+    #[serde(rename = "Title", default)]
+    title: String,
+    #[serde(rename = "Authors", default)]
+    authors: String,
+    #[serde(rename = "ISBN/UID", default)]
+    isbn_uid: String,
+    #[serde(rename = "Read Status", default)]
+    read_status: String,
+    #[serde(rename = "Date Added", default)]
+    date_added: String,
+    #[serde(rename = "Last Date Read", default)]
+    last_date_read: String,
+    #[serde(rename = "Moods", default)]
+    moods: String,
+    #[serde(rename = "Star Rating", default)]
+    star_rating: String,
+    #[serde(rename = "Tags", default)]
+    tags: String,
+}
+
+/// Parse a StoryGraph library export CSV.
+pub fn parse_storygraph_csv(input: &str) -> Result<Vec<LibraryRow>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(input.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Invalid CSV header: {}", e))?;
+    if !headers.iter().any(|h| h == "Title") || !headers.iter().any(|h| h == "Authors") {
+        return Err("Not a StoryGraph export: missing Title or Authors column".to_string());
+    }
+
+    let rows = reader
+        .deserialize::<StoryGraphRecord>()
+        .enumerate()
+        .map(|(index, record)| LibraryRow {
+            row: index + 1,
+            entry: record
+                .map_err(|e| format!("Invalid row: {}", e))
+                .and_then(to_library_entry),
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+/// Write books in the StoryGraph library export layout.
+///
+/// `read_throughs` may cover any books; each book's finished ones give its
+/// "Dates Read" and "Read Count".
+pub fn write_storygraph_csv(
+    books: &[Book],
+    read_throughs: &[ReadThrough],
+) -> Result<String, String> {
+    let mut finishes_by_book: HashMap<i32, Vec<NaiveDate>> = HashMap::new();
+    for read_through in read_throughs {
+        if let Some(finished_on) = read_through.finished_on {
+            finishes_by_book
+                .entry(read_through.book_id)
+                .or_default()
+                .push(finished_on);
+        }
+    }
+    for finishes in finishes_by_book.values_mut() {
+        finishes.sort();
+    }
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(HEADERS)
+        .map_err(|e| format!("Failed to write CSV header: {}", e))?;
+
+    for book in books {
+        let moods: Vec<&str> = book
+            .tags
+            .iter()
+            .filter_map(|tag| tag.strip_prefix(MOOD_TAG_PREFIX))
+            .collect();
+        let tags: Vec<&str> = book
+            .tags
+            .iter()
+            .map(String::as_str)
+            .filter(|tag| !tag.starts_with(MOOD_TAG_PREFIX))
+            .collect();
+        let last_date_read = book
+            .date_finished
+            .map(|date| date.format(DATE_FORMAT).to_string())
+            .unwrap_or_default();
+        let finishes = finishes_by_book
+            .get(&book.id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let dates_read: Vec<String> = finishes
+            .iter()
+            .map(|date| date.format(DATE_FORMAT).to_string())
+            .collect();
+
+        let mut record = vec![String::new(); HEADERS.len()];
+        let mut set = |column: &str, value: String| {
+            if let Some(index) = HEADERS.iter().position(|header| *header == column) {
+                record[index] = value;
+            }
+        };
+        set("Title", book.title.clone());
+        set("Authors", book.author.clone());
+        set(
+            "ISBN/UID",
+            book.isbn13
+                .clone()
+                .or(book.isbn.clone())
+                .unwrap_or_default(),
+        );
+        set(
+            "Read Status",
+            status_to_read_status(book.status).to_string(),
+        );
+        set(
+            "Date Added",
+            book.date_added.format(DATE_FORMAT).to_string(),
+        );
+        set("Last Date Read", last_date_read);
+        set("Dates Read", dates_read.join(", "));
+        set("Read Count", finishes.len().to_string());
+        set("Moods", moods.join(", "));
+        set(
+            "Star Rating",
+            book.rating
+                .map(|rating| format!("{:.1}", rating as f64))
+                .unwrap_or_default(),
+        );
+        set("Tags", tags.join(", "));
+
+        writer
+            .write_record(&record)
+            .map_err(|e| format!("Failed to write CSV row: {}", e))?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| format!("Failed to finish CSV: {}", e))?;
+    String::from_utf8(bytes).map_err(|e| format!("Invalid UTF-8 in CSV: {}", e))
+}
+
+fn status_to_read_status(status: BookStatus) -> &'static str {
+    match status {
+        BookStatus::Reading => "currently-reading",
+        BookStatus::Finished => "read",
+        BookStatus::Wishlist => "to-read",
+        BookStatus::Paused => "paused",
+        BookStatus::Abandoned => "did-not-finish",
+    }
+}
+
+fn to_library_entry(record: StoryGraphRecord) -> Result<LibraryEntry, String> {
+    if record.title.is_empty() {
+        return Err("Missing title".to_string());
+    }
+    if record.authors.is_empty() {
+        return Err("Missing authors".to_string());
+    }
+
+    let status = match record.read_status.as_str() {
+        "read" => Some(BookStatus::Finished),
+        "currently-reading" => Some(BookStatus::Reading),
+        "to-read" => Some(BookStatus::Wishlist),
+        "paused" => Some(BookStatus::Paused),
+        "did-not-finish" => Some(BookStatus::Abandoned),
+        _ => None,
+    };
+
+    // StoryGraph ISBN/UID holds either an ISBN or an internal id
+    let (isbn, isbn13) = match clean_isbn(&record.isbn_uid) {
+        Some(value) if value.len() == 13 => (None, Some(value)),
+        Some(value) if value.len() == 10 => (Some(value), None),
+        _ => (None, None),
+    };
+
+    let mut tags: Vec<String> = Vec::new();
+    let moods = split_list(&record.moods).map(|mood| format!("{}{}", MOOD_TAG_PREFIX, mood));
+    for tag in split_list(&record.tags).map(str::to_string).chain(moods) {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags.truncate(MAX_TAGS);
+
+    Ok(LibraryEntry {
+        title: record.title,
+        author: record.authors,
+        isbn,
+        isbn13,
+        status,
+        rating: parse_star_rating(&record.star_rating)?,
+        date_added: parse_export_date(&record.date_added),
+        date_finished: parse_export_date(&record.last_date_read),
+        tags,
+    })
+}
+
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+/// Map StoryGraph's fractional stars (e.g. `3.5`, `4.25`) onto our 1-5 scale,
+/// rounding half stars up
+fn parse_star_rating(value: &str) -> Result<Option<i32>, String> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    let stars: f64 = value
+        .parse()
+        .map_err(|_| format!("Invalid star rating: {}", value))?;
+    if !(0.0..=5.0).contains(&stars) {
+        return Err(format!("Invalid star rating: {}", value));
+    }
+    if stars == 0.0 {
+        return Ok(None);
+    }
+
+    Ok(Some((stars.round() as i32).clamp(1, 5)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book(status: BookStatus) -> Book {
+        Book {
+            id: 1,
+            title: "The Left Hand of Darkness".to_string(),
+            author: "Ursula K. Le Guin".to_string(),
+            cover_url: None,
+            tags: vec!["fiction/sci-fi".to_string(), "mood/reflective".to_string()],
+            status,
+            date_added: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+            date_started: None,
+            date_finished: NaiveDate::from_ymd_opt(2024, 3, 4),
+            rating: Some(4),
+            description: None,
+            notes_count: 0,
+            isbn: None,
+            isbn13: Some("9780441478125".to_string()),
+            page_count: None,
+            audio_minutes: None,
+            abandon_reason: None,
+            abandon_page: None,
+        }
+    }
+
+    #[test]
+    fn export_then_import_round_trips() {
+        let statuses = [
+            BookStatus::Finished,
+            BookStatus::Reading,
+            BookStatus::Wishlist,
+            BookStatus::Paused,
+            BookStatus::Abandoned,
+        ];
+        let books: Vec<Book> = statuses.iter().map(|status| book(*status)).collect();
+
+        let csv = write_storygraph_csv(&books, &[]).unwrap();
+        let rows = parse_storygraph_csv(&csv).unwrap();
+
+        assert_eq!(rows.len(), books.len());
+        for (row, book) in rows.into_iter().zip(&books) {
+            let expected = LibraryEntry {
+                title: book.title.clone(),
+                author: book.author.clone(),
+                isbn: None,
+                isbn13: book.isbn13.clone(),
+                status: Some(book.status),
+                rating: book.rating,
+                date_added: Some(book.date_added),
+                date_finished: book.date_finished,
+                tags: book.tags.clone(),
+            };
+            assert_eq!(row.entry.unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn counts_finished_read_throughs() {
+        let read_through = |book_id: i32, finished_on: Option<NaiveDate>| ReadThrough {
+            id: 0,
+            book_id,
+            started_on: None,
+            finished_on,
+            rating: None,
+            format: None,
+            created_at: Default::default(),
+            updated_at: Default::default(),
+        };
+        let read_throughs = [
+            read_through(1, NaiveDate::from_ymd_opt(2024, 3, 4)),
+            read_through(2, NaiveDate::from_ymd_opt(2020, 1, 1)),
+            read_through(1, None),
+            read_through(1, NaiveDate::from_ymd_opt(2021, 6, 7)),
+        ];
+
+        let csv = write_storygraph_csv(&[book(BookStatus::Reading)], &read_throughs).unwrap();
+        let mut reader = csv::Reader::from_reader(csv.as_bytes());
+        let record = reader.records().next().unwrap().unwrap();
+        let column = |name: &str| {
+            let index = HEADERS.iter().position(|header| *header == name).unwrap();
+            record[index].to_string()
+        };
+
+        assert_eq!(column("Dates Read"), "2021/06/07, 2024/03/04");
+        assert_eq!(column("Read Count"), "2");
+        assert_eq!(column("Last Date Read"), "2024/03/04");
+    }
+
+    #[test]
+    fn maps_half_stars_onto_whole_ratings() {
+        assert_eq!(parse_star_rating(""), Ok(None));
+        assert_eq!(parse_star_rating("0"), Ok(None));
+        assert_eq!(parse_star_rating("0.5"), Ok(Some(1)));
+        assert_eq!(parse_star_rating("2.5"), Ok(Some(3)));
+        assert_eq!(parse_star_rating("3.5"), Ok(Some(4)));
+        assert_eq!(parse_star_rating("4.25"), Ok(Some(4)));
+        assert_eq!(parse_star_rating("4.75"), Ok(Some(5)));
+        assert_eq!(parse_star_rating("5.0"), Ok(Some(5)));
+        assert!(parse_star_rating("5.5").is_err());
+        assert!(parse_star_rating("four").is_err());
+    }
+}
@@ -1,5 +1,7 @@
 pub mod errors;
+pub mod exporters;
 pub mod handlers;
+pub mod importers;
 pub mod models;
 pub mod routes;
 pub mod services;
@@ -7,7 +9,23 @@ pub mod services;
 pub use errors::*;
 pub use models::*;
 pub use routes::create_api_routes;
-pub use services::{AppState, BookService};
+pub use services::{
+    AppState, BookService, CollectionService, ExportService, GoalService, HighlightService,
+    ImportService, NoteService, ProgressService, ReadThroughService, ReadingSessionService,
+    SearchService, StatsService, TagAliases, TagNormalizer, TagService,
+};
 
 // Re-export for external use
 pub use handlers::books as handlers_books;
+pub use handlers::collections as handlers_collections;
+pub use handlers::exports as handlers_exports;
+pub use handlers::goals as handlers_goals;
+pub use handlers::highlights as handlers_highlights;
+pub use handlers::imports as handlers_imports;
+pub use handlers::notes as handlers_notes;
+pub use handlers::progress as handlers_progress;
+pub use handlers::read_throughs as handlers_read_throughs;
+pub use handlers::reading_sessions as handlers_reading_sessions;
+pub use handlers::search as handlers_search;
+pub use handlers::stats as handlers_stats;
+pub use handlers::tags as handlers_tags;
@@ -25,7 +25,21 @@ async fn main() {
 }
 
 async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let application = Application::build().await?;
-    application.run().await?;
+    match std::env::args().nth(1).as_deref() {
+        // Maintenance command: `book-notes repair-notes-count`
+        Some("repair-notes-count") => {
+            let repaired = startup::repair_notes_counts().await?;
+            println!("Recomputed notes_count, {} book(s) corrected", repaired);
+        }
+        // Maintenance command: `book-notes normalize-tags`
+        Some("normalize-tags") => {
+            let retagged = startup::normalize_tags().await?;
+            println!("Normalized tags, {} book(s) retagged", retagged);
+        }
+        _ => {
+            let application = Application::build().await?;
+            application.run().await?;
+        }
+    }
     Ok(())
 }
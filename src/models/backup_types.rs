@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{Book, Highlight, Note, ReadThrough};
+
+/// Complete, versioned snapshot of the library
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Backup {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub books: Vec<Book>,
+    pub tags: Vec<String>,
+    pub notes: Vec<BackupNote>,
+    pub highlights: Vec<BackupHighlight>,
+    pub read_throughs: Vec<ReadThrough>,
+}
+
+/// A backed-up note, with the key that lets clipping imports recognize it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupNote {
+    #[serde(flatten)]
+    pub note: Note,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub import_key: Option<String>,
+}
+
+/// A backed-up highlight, with the key that lets clipping imports recognize it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupHighlight {
+    #[serde(flatten)]
+    pub highlight: Highlight,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub import_key: Option<String>,
+}
+
+/// How to restore a backed-up book that already exists in the library,
+/// matched by normalized title and author
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictPolicy {
+    /// Keep the existing book and ignore the backed-up one
+    #[default]
+    Skip,
+    /// Replace the existing book, its notes and its highlights
+    Overwrite,
+    /// Restore the backed-up book as a new, separate book
+    Duplicate,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RestoreParams {
+    pub conflict: Option<ConflictPolicy>,
+}
+
+/// Outcome of restoring a backup
+#[derive(Debug, Default, Serialize)]
+pub struct RestoreReport {
+    pub backup_version: u32,
+    pub books_created: usize,
+    pub books_overwritten: usize,
+    pub books_skipped: usize,
+    pub notes_restored: usize,
+    pub highlights_restored: usize,
+    pub read_throughs_restored: usize,
+}
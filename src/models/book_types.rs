@@ -12,19 +12,44 @@ pub struct Book {
     pub tags: Vec<String>,
     pub status: BookStatus,
     pub date_added: NaiveDate,
+    /// Day the current (or last) read began
+    pub date_started: Option<NaiveDate>,
     pub date_finished: Option<NaiveDate>,
     pub rating: Option<i32>,
     pub description: Option<String>,
     pub notes_count: i32,
+    pub isbn: Option<String>,
+    pub isbn13: Option<String>,
+    pub page_count: Option<i32>,
+    /// Length of the audiobook edition, in minutes
+    pub audio_minutes: Option<i32>,
+    /// Why an abandoned book was given up
+    pub abandon_reason: Option<String>,
+    /// Page an abandoned book was given up on
+    pub abandon_page: Option<i32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "book_status", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum BookStatus {
     Reading,
     Finished,
     Wishlist,
+    /// Put down for now, to be picked up again
+    Paused,
+    /// Given up on without finishing
+    Abandoned,
+}
+
+impl BookStatus {
+    /// Whether the book has a read underway, even one on hold or given up
+    pub fn has_open_read(self) -> bool {
+        matches!(
+            self,
+            BookStatus::Reading | BookStatus::Paused | BookStatus::Abandoned
+        )
+    }
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -57,7 +82,30 @@ pub struct CreateBookRequest {
     #[validate(length(max = 2000, message = "Description must be less than 2000 characters"))]
     pub description: Option<String>,
 
+    pub date_started: Option<NaiveDate>,
+
     pub date_finished: Option<NaiveDate>,
+
+    #[validate(length(min = 10, max = 10, message = "ISBN must be 10 characters"))]
+    pub isbn: Option<String>,
+
+    #[validate(length(min = 13, max = 13, message = "ISBN-13 must be 13 characters"))]
+    pub isbn13: Option<String>,
+
+    #[validate(range(min = 1, message = "Page count must be positive"))]
+    pub page_count: Option<i32>,
+
+    #[validate(range(min = 1, message = "Audio length must be positive"))]
+    pub audio_minutes: Option<i32>,
+
+    #[validate(length(
+        max = 2000,
+        message = "Abandon reason must be less than 2000 characters"
+    ))]
+    pub abandon_reason: Option<String>,
+
+    #[validate(range(min = 1, message = "Abandon page must be positive"))]
+    pub abandon_page: Option<i32>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -90,13 +138,146 @@ pub struct UpdateBookRequest {
     #[validate(length(max = 2000, message = "Description must be less than 2000 characters"))]
     pub description: Option<String>,
 
+    pub date_started: Option<NaiveDate>,
+
     pub date_finished: Option<NaiveDate>,
+
+    #[validate(length(min = 10, max = 10, message = "ISBN must be 10 characters"))]
+    pub isbn: Option<String>,
+
+    #[validate(length(min = 13, max = 13, message = "ISBN-13 must be 13 characters"))]
+    pub isbn13: Option<String>,
+
+    #[validate(range(min = 1, message = "Page count must be positive"))]
+    pub page_count: Option<i32>,
+
+    #[validate(range(min = 1, message = "Audio length must be positive"))]
+    pub audio_minutes: Option<i32>,
+
+    #[validate(length(
+        max = 2000,
+        message = "Abandon reason must be less than 2000 characters"
+    ))]
+    pub abandon_reason: Option<String>,
+
+    #[validate(range(min = 1, message = "Abandon page must be positive"))]
+    pub abandon_page: Option<i32>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct BookFilter {
     pub status: Option<BookStatus>,
     pub search: Option<String>,
+    /// Comma-separated list of tags, e.g. `tags=sci-fi,classics`
+    pub tags: Option<String>,
+    /// Whether books must carry any (default) or all of `tags`
+    pub tag_match: Option<TagMatch>,
+    /// Exact author name, compared case-insensitively
+    pub author: Option<String>,
+    pub min_rating: Option<i32>,
+    pub max_rating: Option<i32>,
+    pub added_from: Option<NaiveDate>,
+    pub added_to: Option<NaiveDate>,
+    pub finished_from: Option<NaiveDate>,
+    pub finished_to: Option<NaiveDate>,
+    pub has_cover: Option<bool>,
+    pub has_notes: Option<bool>,
+    /// Only books in this collection
+    pub collection: Option<i32>,
+    /// Comma-separated sort keys, each `field` or `field:asc|desc`,
+    /// e.g. `sort=rating:desc,title`
+    pub sort: Option<String>,
+    /// Opaque `next_cursor` from a previous page; takes precedence over `page`
+    pub cursor: Option<String>,
     pub page: Option<u32>,
     pub limit: Option<u32>,
+    /// Also return per-status, tag, rating and finished-year counts
+    pub facets: Option<bool>,
+}
+
+impl BookFilter {
+    /// Tags from the comma-separated `tags` parameter, trimmed and without blanks
+    pub fn tag_list(&self) -> Vec<String> {
+        self.tags
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// Fields that book listings may be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortField {
+    Title,
+    Author,
+    Rating,
+    DateAdded,
+    DateFinished,
+    NotesCount,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    pub field: SortField,
+    pub direction: SortDirection,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagMatch {
+    #[default]
+    Any,
+    All,
+}
+
+/// A book in a listing, with its fuzzy-match score when a search is active
+#[derive(Debug, Clone, Serialize)]
+pub struct BookListItem {
+    #[serde(flatten)]
+    pub book: Book,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub similarity: Option<f32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BookList {
+    pub items: Vec<BookListItem>,
+    /// Number of books matching the filters across all pages
+    pub total: i64,
+    /// Page number for offset pagination; `None` when paging by cursor
+    pub page: Option<u32>,
+    pub limit: u32,
+    /// Pass as `cursor` to fetch the following page; `None` on the last page
+    pub next_cursor: Option<String>,
+    /// Closest title or author when a search had no exact matches
+    pub suggestion: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facets: Option<BookFacets>,
+}
+
+/// Number of matching books sharing one facet value
+#[derive(Debug, Clone, Serialize)]
+pub struct FacetCount<T> {
+    pub value: T,
+    pub count: i64,
+}
+
+/// Counts for the library sidebar. Each facet applies every active filter
+/// except its own, so picking one value still shows the alternatives.
+#[derive(Debug, Clone, Serialize)]
+pub struct BookFacets {
+    pub status: Vec<FacetCount<BookStatus>>,
+    pub tags: Vec<FacetCount<String>>,
+    pub ratings: Vec<FacetCount<i32>>,
+    pub finished_years: Vec<FacetCount<i32>>,
 }
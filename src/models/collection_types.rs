@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// A user-defined, manually ordered shelf of books
+#[derive(Debug, Clone, Serialize)]
+pub struct Collection {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub position: i32,
+    pub book_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateCollectionRequest {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must be between 1 and 100 characters"
+    ))]
+    pub name: String,
+
+    #[validate(length(max = 2000, message = "Description must be less than 2000 characters"))]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateCollectionRequest {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must be between 1 and 100 characters"
+    ))]
+    pub name: Option<String>,
+
+    #[validate(length(max = 2000, message = "Description must be less than 2000 characters"))]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddCollectionBookRequest {
+    pub book_id: i32,
+}
+
+/// New order for collections, or for the books inside one collection.
+///
+/// `ids` must list every member exactly once.
+#[derive(Debug, Deserialize)]
+pub struct ReorderRequest {
+    pub ids: Vec<i32>,
+}
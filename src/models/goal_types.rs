@@ -0,0 +1,79 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// A target number of books or pages to finish in a year or month
+#[derive(Debug, Clone, Serialize)]
+pub struct Goal {
+    pub id: i32,
+    pub period: GoalPeriod,
+    pub metric: GoalMetric,
+    pub year: i32,
+    /// Month of the year (1-12) for monthly goals
+    pub month: Option<i32>,
+    pub target: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "goal_period", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum GoalPeriod {
+    Yearly,
+    Monthly,
+}
+
+/// What a goal counts among books finished in its period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "goal_metric", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum GoalMetric {
+    Books,
+    Pages,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateGoalRequest {
+    pub period: GoalPeriod,
+    pub metric: GoalMetric,
+
+    #[validate(range(min = 1, max = 9999, message = "Year must be between 1 and 9999"))]
+    pub year: i32,
+
+    #[validate(range(min = 1, max = 12, message = "Month must be between 1 and 12"))]
+    pub month: Option<i32>,
+
+    #[validate(range(min = 1, message = "Target must be positive"))]
+    pub target: i32,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateGoalRequest {
+    #[validate(range(min = 1, message = "Target must be positive"))]
+    pub target: Option<i32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct GoalFilter {
+    pub year: Option<i32>,
+}
+
+/// A goal with how far along it is and where the current pace leads
+#[derive(Debug, Clone, Serialize)]
+pub struct GoalProgress {
+    #[serde(flatten)]
+    pub goal: Goal,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub completed: i64,
+    pub remaining: i64,
+    pub percent: f64,
+    /// Where an even pace through the period would be by today
+    pub expected: i64,
+    pub on_track: bool,
+    /// How far short of `expected` progress is; 0 when on track
+    pub behind_by: i64,
+    /// Total by the end of the period at the pace so far; `None` before it starts
+    pub projected: Option<i64>,
+}
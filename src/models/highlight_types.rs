@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Highlight {
+    pub id: i32,
+    pub book_id: i32,
+    pub text: String,
+    pub page_number: Option<i32>,
+    pub chapter: Option<String>,
+    pub percent: Option<f64>,
+    pub location_start: Option<i32>,
+    pub location_end: Option<i32>,
+    pub color: HighlightColor,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "highlight_color", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum HighlightColor {
+    #[default]
+    Yellow,
+    Blue,
+    Pink,
+    Orange,
+}
+
+/// Ordering for highlight listings
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HighlightSort {
+    /// Position in the book: percent, or page as a share of the page count,
+    /// then e-reader location. Highlights anchored only by location sort after
+    /// the others.
+    #[default]
+    Position,
+    /// Creation time, newest first
+    Created,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateHighlightRequest {
+    #[validate(length(
+        min = 1,
+        max = 10000,
+        message = "Text must be between 1 and 10000 characters"
+    ))]
+    pub text: String,
+
+    #[validate(range(min = 0, message = "Page number must not be negative"))]
+    pub page_number: Option<i32>,
+
+    #[validate(length(max = 255, message = "Chapter must be less than 255 characters"))]
+    pub chapter: Option<String>,
+
+    #[validate(range(min = 0.0, max = 100.0, message = "Percent must be between 0 and 100"))]
+    pub percent: Option<f64>,
+
+    #[validate(range(min = 0, message = "Location must not be negative"))]
+    pub location_start: Option<i32>,
+
+    #[validate(range(min = 0, message = "Location must not be negative"))]
+    pub location_end: Option<i32>,
+
+    pub color: Option<HighlightColor>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateHighlightRequest {
+    #[validate(length(
+        min = 1,
+        max = 10000,
+        message = "Text must be between 1 and 10000 characters"
+    ))]
+    pub text: Option<String>,
+
+    #[validate(range(min = 0, message = "Page number must not be negative"))]
+    pub page_number: Option<i32>,
+
+    #[validate(length(max = 255, message = "Chapter must be less than 255 characters"))]
+    pub chapter: Option<String>,
+
+    #[validate(range(min = 0.0, max = 100.0, message = "Percent must be between 0 and 100"))]
+    pub percent: Option<f64>,
+
+    #[validate(range(min = 0, message = "Location must not be negative"))]
+    pub location_start: Option<i32>,
+
+    #[validate(range(min = 0, message = "Location must not be negative"))]
+    pub location_end: Option<i32>,
+
+    pub color: Option<HighlightColor>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HighlightFilter {
+    pub sort: Option<HighlightSort>,
+}
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+/// Outcome of importing a Kindle `My Clippings.txt` file
+#[derive(Debug, Default, Serialize)]
+pub struct KindleImportReport {
+    pub books_created: usize,
+    pub books_matched: usize,
+    pub highlights_created: usize,
+    pub notes_created: usize,
+    /// Clippings that were already imported previously
+    pub skipped_duplicates: usize,
+    pub skipped_bookmarks: usize,
+    pub skipped_malformed: usize,
+}
+
+/// What a library import did (or would do, in a dry run) with one row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportAction {
+    Create,
+    Update,
+    Unchanged,
+    Error,
+}
+
+/// A single field difference between the library and an imported row
+#[derive(Debug, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub from: serde_json::Value,
+    pub to: serde_json::Value,
+}
+
+/// Per-row outcome of a library import
+#[derive(Debug, Serialize)]
+pub struct ImportRowResult {
+    pub row: usize,
+    pub title: Option<String>,
+    pub author: Option<String>,
+    /// Matched or created book; not set for books a dry run would create
+    pub book_id: Option<i32>,
+    pub action: ImportAction,
+    pub changes: Vec<FieldChange>,
+    pub error: Option<String>,
+}
+
+/// Outcome of importing a library export such as a Goodreads CSV
+#[derive(Debug, Default, Serialize)]
+pub struct LibraryImportReport {
+    pub dry_run: bool,
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub failed: usize,
+    pub rows: Vec<ImportRowResult>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LibraryImportParams {
+    /// Preview the import without writing anything; defaults to `true`
+    pub dry_run: Option<bool>,
+}
@@ -1,7 +1,55 @@
+pub mod backup_types;
 pub mod book_types;
+pub mod collection_types;
+pub mod goal_types;
+pub mod highlight_types;
+pub mod import_types;
+pub mod note_types;
+pub mod progress_types;
+pub mod read_through_types;
+pub mod reading_session_types;
+pub mod search_types;
+pub mod stats_types;
+pub mod tag_types;
+
+// Re-export all backup-related types
+pub use backup_types::*;
 
 // Re-export all book-related types and traits
 pub use book_types::*;
 
+// Re-export all collection-related types
+pub use collection_types::*;
+
+// Re-export all goal-related types
+pub use goal_types::*;
+
+// Re-export all highlight-related types
+pub use highlight_types::*;
+
+// Re-export all import-related types
+pub use import_types::*;
+
+// Re-export all note-related types
+pub use note_types::*;
+
+// Re-export all progress-related types
+pub use progress_types::*;
+
+// Re-export all read-through types
+pub use read_through_types::*;
+
+// Re-export all reading session types
+pub use reading_session_types::*;
+
+// Re-export all search-related types
+pub use search_types::*;
+
+// Re-export all statistics types
+pub use stats_types::*;
+
+// Re-export all tag-related types
+pub use tag_types::*;
+
 // Re-export validator trait for validation
 pub use validator::Validate;
@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Note {
+    pub id: i32,
+    pub book_id: i32,
+    pub content: String,
+    pub page_number: Option<i32>,
+    pub chapter: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateNoteRequest {
+    #[validate(length(
+        min = 1,
+        max = 10000,
+        message = "Content must be between 1 and 10000 characters"
+    ))]
+    pub content: String,
+
+    #[validate(range(min = 0, message = "Page number must not be negative"))]
+    pub page_number: Option<i32>,
+
+    #[validate(length(max = 255, message = "Chapter must be less than 255 characters"))]
+    pub chapter: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateNoteRequest {
+    /// Moves the note to another book when set
+    pub book_id: Option<i32>,
+
+    #[validate(length(
+        min = 1,
+        max = 10000,
+        message = "Content must be between 1 and 10000 characters"
+    ))]
+    pub content: Option<String>,
+
+    #[validate(range(min = 0, message = "Page number must not be negative"))]
+    pub page_number: Option<i32>,
+
+    #[validate(length(max = 255, message = "Chapter must be less than 255 characters"))]
+    pub chapter: Option<String>,
+}
@@ -0,0 +1,50 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::Book;
+
+/// A single progress update for a book
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadingProgress {
+    pub id: i32,
+    pub book_id: i32,
+    pub current_page: Option<i32>,
+    /// As recorded, or derived from `current_page` and the book's page count
+    pub percent: Option<f64>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RecordProgressRequest {
+    #[validate(range(min = 0, message = "Current page must not be negative"))]
+    pub current_page: Option<i32>,
+
+    #[validate(range(min = 0.0, max = 100.0, message = "Percent must be between 0 and 100"))]
+    pub percent: Option<f64>,
+
+    /// Defaults to now; set it to back-fill earlier sessions
+    pub recorded_at: Option<DateTime<Utc>>,
+}
+
+/// Where a book in progress stands and when it should be finished
+#[derive(Debug, Clone, Serialize)]
+pub struct ProgressSummary {
+    pub current_page: Option<i32>,
+    pub percent: Option<f64>,
+    pub updated_at: DateTime<Utc>,
+    /// Average progress per day across the current read's updates
+    pub percent_per_day: Option<f64>,
+    /// Projected finish date at the current pace
+    pub estimated_completion: Option<NaiveDate>,
+}
+
+/// A book with its reading progress, returned by `GET /api/books/:id`
+#[derive(Debug, Clone, Serialize)]
+pub struct BookDetail {
+    #[serde(flatten)]
+    pub book: Book,
+    /// Present only for books currently being read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<ProgressSummary>,
+}
@@ -0,0 +1,65 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use validator::Validate;
+
+/// One reading of a book, from start to finish. A book may have several.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadThrough {
+    pub id: i32,
+    pub book_id: i32,
+    pub started_on: Option<NaiveDate>,
+    /// `None` while the read-through is in progress
+    pub finished_on: Option<NaiveDate>,
+    pub rating: Option<i32>,
+    pub format: Option<BookFormat>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Edition a book was read in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "book_format", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum BookFormat {
+    Print,
+    Ebook,
+    Audiobook,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateReadThroughRequest {
+    pub started_on: Option<NaiveDate>,
+    pub finished_on: Option<NaiveDate>,
+
+    #[validate(range(min = 1, max = 5, message = "Rating must be between 1 and 5"))]
+    pub rating: Option<i32>,
+
+    pub format: Option<BookFormat>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateReadThroughRequest {
+    pub started_on: Option<NaiveDate>,
+    /// An explicit `null` reopens a finished read-through
+    #[serde(default, deserialize_with = "explicit_null")]
+    pub finished_on: Nullable<NaiveDate>,
+
+    #[validate(range(min = 1, max = 5, message = "Rating must be between 1 and 5"))]
+    pub rating: Option<i32>,
+
+    pub format: Option<BookFormat>,
+}
+
+/// A field left out (`None`), sent as `null` (`Some(None)`) or set
+pub type Nullable<T> = Option<Option<T>>;
+
+type NullableResult<T, E> = Result<Nullable<T>, E>;
+
+/// Tell a field sent as `null` apart from one left out
+fn explicit_null<'de, D, T>(deserializer: D) -> NullableResult<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::deserialize(deserializer).map(Some)
+}
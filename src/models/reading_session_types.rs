@@ -0,0 +1,71 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// One sitting with a book
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadingSession {
+    pub id: i32,
+    pub book_id: i32,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub duration_minutes: i64,
+    pub pages_read: Option<i32>,
+    /// Where the session happened, e.g. `commute`
+    pub location: Option<String>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateReadingSessionRequest {
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+
+    #[validate(range(min = 0, message = "Pages read must not be negative"))]
+    pub pages_read: Option<i32>,
+
+    #[validate(length(max = 255, message = "Location must be less than 255 characters"))]
+    pub location: Option<String>,
+
+    #[validate(length(max = 10000, message = "Note must be less than 10000 characters"))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateReadingSessionRequest {
+    pub started_at: Option<DateTime<Utc>>,
+    pub ended_at: Option<DateTime<Utc>>,
+
+    #[validate(range(min = 0, message = "Pages read must not be negative"))]
+    pub pages_read: Option<i32>,
+
+    #[validate(length(max = 255, message = "Location must be less than 255 characters"))]
+    pub location: Option<String>,
+
+    #[validate(length(max = 10000, message = "Note must be less than 10000 characters"))]
+    pub note: Option<String>,
+}
+
+/// Sessions to include, by book and by the day they started (inclusive)
+#[derive(Debug, Default, Deserialize)]
+pub struct ReadingSessionFilter {
+    pub book_id: Option<i32>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+/// Time spent, reading speed and streaks over a set of sessions
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadingSessionSummary {
+    pub sessions: i64,
+    pub total_minutes: i64,
+    pub total_pages: i64,
+    /// Pages per hour over sessions that recorded pages read
+    pub pages_per_hour: Option<f64>,
+    pub days_read: i64,
+    /// Consecutive days with a session, ending today or yesterday
+    pub current_streak_days: i64,
+    pub longest_streak_days: i64,
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Kind of record a search result points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchKind {
+    Book,
+    Note,
+    Highlight,
+}
+
+/// A ranked full-text search hit; `kind` tells which table `id` belongs to
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResult {
+    pub kind: SearchKind,
+    pub id: i32,
+    pub book_id: i32,
+    pub book_title: String,
+    pub book_author: String,
+    /// Matching excerpt with hits wrapped in `<mark>` tags
+    pub snippet: String,
+    pub rank: f32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchParams {
+    pub q: String,
+    pub limit: Option<u32>,
+}
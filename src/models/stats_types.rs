@@ -0,0 +1,41 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::{BookStatus, FacetCount};
+
+/// Reads to include, by the day they were finished (inclusive)
+#[derive(Debug, Default, Deserialize)]
+pub struct StatsFilter {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    /// How many authors and tags to rank, 10 by default
+    pub top: Option<i64>,
+}
+
+/// Books and pages finished in one month (`2025-03`) or year (`2025`)
+#[derive(Debug, Clone, Serialize)]
+pub struct PeriodCount {
+    pub period: String,
+    pub books: i64,
+    pub pages: i64,
+}
+
+/// Aggregates over finished reads for the analysis page. A book finished
+/// more than once counts once per read.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadingStats {
+    pub books_finished: i64,
+    pub pages_read: i64,
+    pub average_rating: Option<f64>,
+    /// Days from starting to finishing. Reads with no start date count from
+    /// the day the book was added, unless that is after they finished.
+    pub average_days_to_finish: Option<f64>,
+    pub finished_per_month: Vec<PeriodCount>,
+    pub finished_per_year: Vec<PeriodCount>,
+    /// Every rating from 1 to 5, including those no book received
+    pub rating_distribution: Vec<FacetCount<i32>>,
+    pub top_authors: Vec<FacetCount<String>>,
+    pub top_tags: Vec<FacetCount<String>>,
+    /// Current status of every book in the library, regardless of dates
+    pub books_by_status: Vec<FacetCount<BookStatus>>,
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// A tag and the number of books carrying it
+#[derive(Debug, Clone, Serialize)]
+pub struct TagUsage {
+    pub name: String,
+    pub book_count: i64,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RenameTagRequest {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Tag must be between 1 and 100 characters"
+    ))]
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct MergeTagsRequest {
+    /// Tags to fold into `target`; they disappear from every book
+    #[validate(length(min = 1, message = "At least one source tag is required"))]
+    pub sources: Vec<String>,
+
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Tag must be between 1 and 100 characters"
+    ))]
+    pub target: String,
+}
+
+/// Outcome of a library-wide tag change
+#[derive(Debug, Clone, Serialize)]
+pub struct TagChangeReport {
+    pub books_updated: u64,
+}
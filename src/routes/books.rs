@@ -1,7 +1,4 @@
-use axum::{
-    routing::{delete, get, patch, post, put},
-    Router,
-};
+use axum::{routing::get, Router};
 
 use crate::handlers::books::{
     create_book, delete_book, get_book_by_id, get_books, patch_book, update_book,
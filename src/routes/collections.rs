@@ -0,0 +1,39 @@
+use axum::{
+    routing::{delete, get, put},
+    Router,
+};
+
+use crate::handlers::collections::{
+    add_collection_book, create_collection, delete_collection, get_collection_books,
+    get_collection_by_id, get_collections, remove_collection_book, reorder_collection_books,
+    reorder_collections, update_collection,
+};
+use crate::services::AppState;
+
+pub fn create_collection_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/collections",
+            get(get_collections).post(create_collection),
+        )
+        .route("/api/collections/order", put(reorder_collections))
+        .route(
+            "/api/collections/:id",
+            get(get_collection_by_id)
+                .put(update_collection)
+                .patch(update_collection)
+                .delete(delete_collection),
+        )
+        .route(
+            "/api/collections/:id/books",
+            get(get_collection_books).post(add_collection_book),
+        )
+        .route(
+            "/api/collections/:id/books/order",
+            put(reorder_collection_books),
+        )
+        .route(
+            "/api/collections/:id/books/:book_id",
+            delete(remove_collection_book),
+        )
+}
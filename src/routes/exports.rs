@@ -0,0 +1,11 @@
+use axum::{routing::get, Router};
+
+use crate::handlers::exports::{export_backup, export_markdown, export_storygraph};
+use crate::services::AppState;
+
+pub fn create_export_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/export", get(export_backup))
+        .route("/api/export/markdown", get(export_markdown))
+        .route("/api/export/storygraph", get(export_storygraph))
+}
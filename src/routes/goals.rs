@@ -0,0 +1,16 @@
+use axum::{routing::get, Router};
+
+use crate::handlers::goals::{create_goal, delete_goal, get_goal_by_id, get_goals, update_goal};
+use crate::services::AppState;
+
+pub fn create_goal_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/goals", get(get_goals).post(create_goal))
+        .route(
+            "/api/goals/:id",
+            get(get_goal_by_id)
+                .put(update_goal)
+                .patch(update_goal)
+                .delete(delete_goal),
+        )
+}
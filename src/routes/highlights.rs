@@ -0,0 +1,21 @@
+use axum::{routing::get, Router};
+
+use crate::handlers::highlights::{
+    create_highlight, delete_highlight, get_book_highlights, get_highlight_by_id, update_highlight,
+};
+use crate::services::AppState;
+
+pub fn create_highlight_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/books/:id/highlights",
+            get(get_book_highlights).post(create_highlight),
+        )
+        .route(
+            "/api/highlights/:id",
+            get(get_highlight_by_id)
+                .put(update_highlight)
+                .patch(update_highlight)
+                .delete(delete_highlight),
+        )
+}
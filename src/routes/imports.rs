@@ -0,0 +1,18 @@
+use axum::{extract::DefaultBodyLimit, routing::post, Router};
+
+use crate::handlers::imports::{
+    import_goodreads, import_kindle_clippings, import_storygraph, restore_backup,
+};
+use crate::services::AppState;
+
+/// Clippings and library exports can easily exceed axum's 2 MB default
+const IMPORT_BODY_LIMIT: usize = 20 * 1024 * 1024;
+
+pub fn create_import_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/import", post(restore_backup))
+        .route("/api/import/kindle", post(import_kindle_clippings))
+        .route("/api/import/goodreads", post(import_goodreads))
+        .route("/api/import/storygraph", post(import_storygraph))
+        .layer(DefaultBodyLimit::max(IMPORT_BODY_LIMIT))
+}
@@ -1,15 +1,51 @@
 pub mod books;
+pub mod collections;
+pub mod exports;
+pub mod goals;
+pub mod highlights;
+pub mod imports;
+pub mod notes;
+pub mod progress;
+pub mod read_throughs;
+pub mod reading_sessions;
+pub mod search;
+pub mod stats;
+pub mod tags;
 
 use crate::services::AppState;
 use axum::Router;
 
 pub use books::create_book_routes;
+pub use collections::create_collection_routes;
+pub use exports::create_export_routes;
+pub use goals::create_goal_routes;
+pub use highlights::create_highlight_routes;
+pub use imports::create_import_routes;
+pub use notes::create_note_routes;
+pub use progress::create_progress_routes;
+pub use read_throughs::create_read_through_routes;
+pub use reading_sessions::create_reading_session_routes;
+pub use search::create_search_routes;
+pub use stats::create_stats_routes;
+pub use tags::create_tag_routes;
 
 /// Creates the main API router that combines all domain routers
 pub fn create_api_routes() -> Router<AppState> {
-    Router::new().merge(books::create_book_routes())
+    Router::new()
+        .merge(books::create_book_routes())
+        .merge(notes::create_note_routes())
+        .merge(highlights::create_highlight_routes())
+        .merge(imports::create_import_routes())
+        .merge(exports::create_export_routes())
+        .merge(search::create_search_routes())
+        .merge(tags::create_tag_routes())
+        .merge(collections::create_collection_routes())
+        .merge(progress::create_progress_routes())
+        .merge(reading_sessions::create_reading_session_routes())
+        .merge(read_throughs::create_read_through_routes())
+        .merge(stats::create_stats_routes())
+        .merge(goals::create_goal_routes())
     // Future routers can be added here:
-    // .merge(notes::create_note_routes())
     // .merge(users::create_user_routes())
     // .merge(auth::create_auth_routes())
 }
@@ -0,0 +1,21 @@
+use axum::{routing::get, Router};
+
+use crate::handlers::notes::{
+    create_note, delete_note, get_book_notes, get_note_by_id, update_note,
+};
+use crate::services::AppState;
+
+pub fn create_note_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/books/:id/notes",
+            get(get_book_notes).post(create_note),
+        )
+        .route(
+            "/api/notes/:id",
+            get(get_note_by_id)
+                .put(update_note)
+                .patch(update_note)
+                .delete(delete_note),
+        )
+}
@@ -0,0 +1,11 @@
+use axum::{routing::get, Router};
+
+use crate::handlers::progress::{get_book_progress, record_progress};
+use crate::services::AppState;
+
+pub fn create_progress_routes() -> Router<AppState> {
+    Router::new().route(
+        "/api/books/:id/progress",
+        get(get_book_progress).post(record_progress),
+    )
+}
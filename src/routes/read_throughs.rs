@@ -0,0 +1,22 @@
+use axum::{routing::get, Router};
+
+use crate::handlers::read_throughs::{
+    create_read_through, delete_read_through, get_book_read_throughs, get_read_through_by_id,
+    update_read_through,
+};
+use crate::services::AppState;
+
+pub fn create_read_through_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/books/:id/read-throughs",
+            get(get_book_read_throughs).post(create_read_through),
+        )
+        .route(
+            "/api/read-throughs/:id",
+            get(get_read_through_by_id)
+                .put(update_read_through)
+                .patch(update_read_through)
+                .delete(delete_read_through),
+        )
+}
@@ -0,0 +1,24 @@
+use axum::{routing::get, Router};
+
+use crate::handlers::reading_sessions::{
+    create_session, delete_session, get_book_sessions, get_session_by_id, get_session_summary,
+    get_sessions, update_session,
+};
+use crate::services::AppState;
+
+pub fn create_reading_session_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/books/:id/sessions",
+            get(get_book_sessions).post(create_session),
+        )
+        .route("/api/sessions", get(get_sessions))
+        .route("/api/sessions/summary", get(get_session_summary))
+        .route(
+            "/api/sessions/:id",
+            get(get_session_by_id)
+                .put(update_session)
+                .patch(update_session)
+                .delete(delete_session),
+        )
+}
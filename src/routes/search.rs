@@ -0,0 +1,8 @@
+use axum::{routing::get, Router};
+
+use crate::handlers::search::search;
+use crate::services::AppState;
+
+pub fn create_search_routes() -> Router<AppState> {
+    Router::new().route("/api/search", get(search))
+}
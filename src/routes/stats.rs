@@ -0,0 +1,8 @@
+use axum::{routing::get, Router};
+
+use crate::handlers::stats::get_stats;
+use crate::services::AppState;
+
+pub fn create_stats_routes() -> Router<AppState> {
+    Router::new().route("/api/stats", get(get_stats))
+}
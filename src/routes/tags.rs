@@ -0,0 +1,17 @@
+use axum::{
+    routing::{get, post, put},
+    Router,
+};
+
+use crate::handlers::tags::{delete_tag, get_tags, merge_tags, rename_tag};
+use crate::services::AppState;
+
+pub fn create_tag_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/tags", get(get_tags))
+        .route("/api/tags/merge", post(merge_tags))
+        .route(
+            "/api/tags/:name",
+            put(rename_tag).patch(rename_tag).delete(delete_tag),
+        )
+}
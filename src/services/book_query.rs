@@ -0,0 +1,867 @@
+//! SQL for listing books, composed with `sqlx::QueryBuilder` so every
+//! user-supplied value is sent as a bound parameter
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Postgres, QueryBuilder};
+
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{Book, BookFilter, SortDirection, SortField, SortKey, TagMatch};
+
+/// Columns selected for every `Book` row, in the order `row_to_book` expects
+pub(crate) const BOOK_COLUMNS: &str = "id, title, author, cover_url, tags, status::text, date_added, date_started, date_finished, rating, description, notes_count, isbn, isbn13, page_count, audio_minutes, abandon_reason, abandon_page";
+
+/// A search string prepared for both full-text and trigram matching
+pub(crate) struct SearchTerms {
+    /// Trimmed input, compared against `title` and `author` with `pg_trgm`
+    pub raw: String,
+    /// Prefix `to_tsquery` expression for `search_vector`
+    pub tsquery: String,
+}
+
+impl SearchTerms {
+    pub(crate) fn from_filter(filter: &BookFilter) -> Option<Self> {
+        let raw = filter.search.as_deref()?.trim();
+        Some(Self {
+            raw: raw.to_string(),
+            tsquery: prefix_tsquery(raw)?,
+        })
+    }
+}
+
+/// Build the `SELECT` behind `GET /api/books`.
+///
+/// Besides the book columns it selects `similarity` (best trigram word
+/// similarity against title or author) and `exact_match` (full-text hit), both
+/// only meaningful when a search is active. `sort` is the effective ordering
+/// from [`effective_sort`], with `None` meaning search relevance.
+pub(crate) fn book_list_query(
+    filter: &BookFilter,
+    sort: Option<&[SortKey]>,
+    after: Option<&Cursor>,
+    limit: i64,
+    offset: i64,
+) -> QueryBuilder<'static, Postgres> {
+    let mut builder = QueryBuilder::new(format!("SELECT {}", BOOK_COLUMNS));
+    match SearchTerms::from_filter(filter) {
+        Some(terms) => {
+            builder
+                .push(", GREATEST(word_similarity(")
+                .push_bind(terms.raw.clone())
+                .push(", title), word_similarity(")
+                .push_bind(terms.raw)
+                .push(", author)) AS similarity, search_vector @@ to_tsquery('english', ")
+                .push_bind(terms.tsquery)
+                .push(") AS exact_match");
+        }
+        None => {
+            builder.push(", NULL::REAL AS similarity, TRUE AS exact_match");
+        }
+    }
+    builder.push(" FROM books WHERE 1=1");
+    push_book_filters(&mut builder, filter);
+
+    if let (Some(keys), Some(cursor)) = (sort, after) {
+        push_cursor_condition(&mut builder, keys, cursor);
+    }
+
+    push_order_by(&mut builder, sort);
+    builder
+        .push(" LIMIT ")
+        .push_bind(limit)
+        .push(" OFFSET ")
+        .push_bind(offset);
+
+    builder
+}
+
+/// Count the books matching `filter`, ignoring pagination.
+///
+/// Also selects `has_exact_match`, whether any of them is a full-text hit for
+/// the search (always true without one).
+pub(crate) fn book_count_query(filter: &BookFilter) -> QueryBuilder<'static, Postgres> {
+    let mut builder = QueryBuilder::new("SELECT COUNT(*) AS total, ");
+    match SearchTerms::from_filter(filter) {
+        Some(terms) => {
+            builder
+                .push("COALESCE(bool_or(search_vector @@ to_tsquery('english', ")
+                .push_bind(terms.tsquery)
+                .push(")), FALSE) AS has_exact_match");
+        }
+        None => {
+            builder.push("TRUE AS has_exact_match");
+        }
+    }
+    builder.push(" FROM books WHERE 1=1");
+    push_book_filters(&mut builder, filter);
+    builder
+}
+
+/// Sidebar facets of the book listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Facet {
+    Status,
+    Tag,
+    Rating,
+    FinishedYear,
+}
+
+/// Count matching books per value of `facet`, selected as `value` and `count`.
+///
+/// The facet's own filter is dropped so the other values stay visible.
+pub(crate) fn facet_query(filter: &BookFilter, facet: Facet) -> QueryBuilder<'static, Postgres> {
+    let mut others = filter.clone();
+    let (value, from, condition, order) = match facet {
+        Facet::Status => {
+            others.status = None;
+            ("status", "books", " AND status IS NOT NULL", "value")
+        }
+        Facet::Tag => {
+            others.tags = None;
+            ("tag", "books, unnest(tags) AS tag", "", "count DESC, value")
+        }
+        Facet::Rating => {
+            others.min_rating = None;
+            others.max_rating = None;
+            ("rating", "books", " AND rating IS NOT NULL", "value DESC")
+        }
+        Facet::FinishedYear => {
+            others.finished_from = None;
+            others.finished_to = None;
+            (
+                "EXTRACT(YEAR FROM date_finished)::INT",
+                "books",
+                " AND date_finished IS NOT NULL",
+                "value DESC",
+            )
+        }
+    };
+
+    let mut builder = QueryBuilder::new(format!(
+        "SELECT {} AS value, COUNT(*) AS count FROM {} WHERE 1=1{}",
+        value, from, condition
+    ));
+    push_book_filters(&mut builder, &others);
+    builder.push(format!(" GROUP BY value ORDER BY {}", order));
+    builder
+}
+
+/// Resolve the ordering for a listing.
+///
+/// Explicit sort keys win; otherwise searches are ordered by relevance (`None`)
+/// and plain listings by newest first.
+pub(crate) fn effective_sort(sort: Vec<SortKey>, searching: bool) -> Option<Vec<SortKey>> {
+    match (sort.is_empty(), searching) {
+        (false, _) => Some(sort),
+        (true, true) => None,
+        (true, false) => Some(vec![SortKey {
+            field: SortField::DateAdded,
+            direction: SortDirection::Desc,
+        }]),
+    }
+}
+
+/// Append the `ORDER BY` clause. `id` always breaks ties, in the direction of
+/// the last key, so paging never skips or repeats a book.
+fn push_order_by(builder: &mut QueryBuilder<'static, Postgres>, sort: Option<&[SortKey]>) {
+    builder.push(" ORDER BY ");
+
+    let Some(keys) = sort else {
+        builder.push("exact_match DESC, similarity DESC, date_added DESC, id DESC");
+        return;
+    };
+
+    for key in keys {
+        builder.push(format!(
+            "{} {} NULLS LAST, ",
+            sort_column(key.field),
+            direction_sql(key.direction)
+        ));
+    }
+
+    builder.push(format!("id {}", direction_sql(tiebreak_direction(keys))));
+}
+
+fn tiebreak_direction(keys: &[SortKey]) -> SortDirection {
+    keys.last().map_or(SortDirection::Desc, |key| key.direction)
+}
+
+/// Restrict to rows strictly after `cursor` in `keys` order.
+///
+/// Expands to `(k1 after v1) OR (k1 = v1 AND k2 after v2) OR ... OR (all equal
+/// AND id after last_id)`, treating NULLs as sorting last in both directions.
+fn push_cursor_condition(
+    builder: &mut QueryBuilder<'static, Postgres>,
+    keys: &[SortKey],
+    cursor: &Cursor,
+) {
+    builder.push(" AND (");
+
+    for position in 0..=keys.len() {
+        if position > 0 {
+            builder.push(" OR ");
+        }
+        builder.push("(");
+
+        for (key, value) in keys.iter().zip(&cursor.values).take(position) {
+            let column = sort_column(key.field);
+            match value {
+                SortValue::Null => builder.push(format!("{} IS NULL", column)),
+                value => {
+                    builder.push(format!("{} = ", column));
+                    push_sort_value(builder, value)
+                }
+            };
+            builder.push(" AND ");
+        }
+
+        match (keys.get(position), cursor.values.get(position)) {
+            (Some(_), Some(SortValue::Null)) => {
+                // Nothing sorts after NULL when NULLs come last
+                builder.push("FALSE");
+            }
+            (Some(key), Some(value)) => {
+                let column = sort_column(key.field);
+                builder.push(format!("({} {} ", column, after_operator(key.direction)));
+                push_sort_value(builder, value);
+                builder.push(format!(" OR {} IS NULL)", column));
+            }
+            _ => {
+                builder
+                    .push(format!("id {} ", after_operator(tiebreak_direction(keys))))
+                    .push_bind(cursor.id);
+            }
+        }
+
+        builder.push(")");
+    }
+
+    builder.push(")");
+}
+
+fn after_operator(direction: SortDirection) -> &'static str {
+    match direction {
+        SortDirection::Asc => ">",
+        SortDirection::Desc => "<",
+    }
+}
+
+fn push_sort_value<'b>(
+    builder: &'b mut QueryBuilder<'static, Postgres>,
+    value: &SortValue,
+) -> &'b mut QueryBuilder<'static, Postgres> {
+    match value {
+        SortValue::Null => builder.push("NULL"),
+        SortValue::Text(text) => builder.push("lower(").push_bind(text.clone()).push(")"),
+        SortValue::Int(number) => builder.push_bind(*number),
+        SortValue::Date(date) => builder.push_bind(*date),
+    }
+}
+
+/// Value of one sort key in the last row of a page
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SortValue {
+    Null,
+    Text(String),
+    Int(i32),
+    Date(NaiveDate),
+}
+
+/// Position just after the last row of a page, for keyset pagination
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Cursor {
+    values: Vec<SortValue>,
+    id: i32,
+}
+
+/// Wire format of a cursor; `sort` ties it to the ordering it was issued for
+#[derive(Serialize, Deserialize)]
+struct CursorToken {
+    sort: String,
+    values: Vec<Value>,
+    id: i32,
+}
+
+impl Cursor {
+    /// Cursor pointing just after `book` in `keys` order
+    pub(crate) fn after(book: &Book, keys: &[SortKey]) -> Self {
+        let values = keys
+            .iter()
+            .map(|key| match key.field {
+                SortField::Title => SortValue::Text(book.title.clone()),
+                SortField::Author => SortValue::Text(book.author.clone()),
+                SortField::Rating => book.rating.map_or(SortValue::Null, SortValue::Int),
+                SortField::DateAdded => SortValue::Date(book.date_added),
+                SortField::DateFinished => {
+                    book.date_finished.map_or(SortValue::Null, SortValue::Date)
+                }
+                SortField::NotesCount => SortValue::Int(book.notes_count),
+            })
+            .collect();
+
+        Self {
+            values,
+            id: book.id,
+        }
+    }
+
+    pub(crate) fn encode(&self, keys: &[SortKey]) -> String {
+        let token = CursorToken {
+            sort: sort_signature(keys),
+            values: self
+                .values
+                .iter()
+                .map(|value| match value {
+                    SortValue::Null => Value::Null,
+                    SortValue::Text(text) => Value::from(text.clone()),
+                    SortValue::Int(number) => Value::from(*number),
+                    SortValue::Date(date) => Value::from(date.format("%Y-%m-%d").to_string()),
+                })
+                .collect(),
+            id: self.id,
+        };
+
+        // Serializing plain strings, numbers and nulls cannot fail
+        let json = serde_json::to_vec(&token).unwrap_or_default();
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    /// Decode a cursor, rejecting tokens that are malformed or were issued for
+    /// a different sort order
+    pub(crate) fn decode(token: &str, keys: &[SortKey]) -> ApiResult<Self> {
+        let invalid = || ApiError::BadRequest("Invalid cursor".to_string());
+
+        let json = URL_SAFE_NO_PAD
+            .decode(token.trim())
+            .map_err(|_| invalid())?;
+        let token: CursorToken = serde_json::from_slice(&json).map_err(|_| invalid())?;
+
+        if token.sort != sort_signature(keys) {
+            return Err(ApiError::BadRequest(
+                "Cursor was issued for a different sort order".to_string(),
+            ));
+        }
+        if token.values.len() != keys.len() {
+            return Err(invalid());
+        }
+
+        let values = keys
+            .iter()
+            .zip(token.values)
+            .map(|(key, value)| sort_value_from_json(key.field, value).ok_or_else(invalid))
+            .collect::<ApiResult<Vec<_>>>()?;
+
+        Ok(Self {
+            values,
+            id: token.id,
+        })
+    }
+}
+
+fn sort_value_from_json(field: SortField, value: Value) -> Option<SortValue> {
+    if value.is_null() {
+        return Some(SortValue::Null);
+    }
+
+    match field {
+        SortField::Title | SortField::Author => {
+            value.as_str().map(|text| SortValue::Text(text.to_string()))
+        }
+        SortField::Rating | SortField::NotesCount => value
+            .as_i64()
+            .and_then(|number| i32::try_from(number).ok())
+            .map(SortValue::Int),
+        SortField::DateAdded | SortField::DateFinished => value
+            .as_str()
+            .and_then(|text| NaiveDate::parse_from_str(text, "%Y-%m-%d").ok())
+            .map(SortValue::Date),
+    }
+}
+
+/// Canonical `field:direction,...` form of a sort order
+fn sort_signature(keys: &[SortKey]) -> String {
+    keys.iter()
+        .map(|key| {
+            let direction = match key.direction {
+                SortDirection::Asc => "asc",
+                SortDirection::Desc => "desc",
+            };
+            format!("{}:{}", sort_field_name(key.field), direction)
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Sortable fields by their `sort` parameter name
+const SORT_FIELDS: [(&str, SortField); 6] = [
+    ("title", SortField::Title),
+    ("author", SortField::Author),
+    ("rating", SortField::Rating),
+    ("date_added", SortField::DateAdded),
+    ("date_finished", SortField::DateFinished),
+    ("notes_count", SortField::NotesCount),
+];
+
+fn sort_field_name(field: SortField) -> &'static str {
+    SORT_FIELDS
+        .iter()
+        .find(|(_, candidate)| *candidate == field)
+        .map_or("", |(name, _)| name)
+}
+
+/// Whitelisted SQL expression for each sortable field
+fn sort_column(field: SortField) -> &'static str {
+    match field {
+        SortField::Title => "lower(title)",
+        SortField::Author => "lower(author)",
+        SortField::Rating => "rating",
+        SortField::DateAdded => "date_added",
+        SortField::DateFinished => "date_finished",
+        SortField::NotesCount => "notes_count",
+    }
+}
+
+fn direction_sql(direction: SortDirection) -> &'static str {
+    match direction {
+        SortDirection::Asc => "ASC",
+        SortDirection::Desc => "DESC",
+    }
+}
+
+/// Parse the `sort` parameter, rejecting unknown fields and directions
+pub(crate) fn parse_sort(sort: Option<&str>) -> ApiResult<Vec<SortKey>> {
+    let Some(sort) = sort else {
+        return Ok(Vec::new());
+    };
+
+    let mut keys: Vec<SortKey> = Vec::new();
+    for part in sort
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+    {
+        let (name, direction) = part.split_once(':').unwrap_or((part, "asc"));
+
+        let field = SORT_FIELDS
+            .iter()
+            .find(|(field_name, _)| *field_name == name.trim())
+            .map(|(_, field)| *field)
+            .ok_or_else(|| {
+                let names: Vec<&str> = SORT_FIELDS.iter().map(|(name, _)| *name).collect();
+                ApiError::BadRequest(format!(
+                    "Unknown sort field '{}'; expected one of {}",
+                    name.trim(),
+                    names.join(", ")
+                ))
+            })?;
+
+        let direction = match direction.trim().to_lowercase().as_str() {
+            "asc" => SortDirection::Asc,
+            "desc" => SortDirection::Desc,
+            other => {
+                return Err(ApiError::BadRequest(format!(
+                    "Unknown sort direction '{}'; expected asc or desc",
+                    other
+                )))
+            }
+        };
+
+        if keys.iter().any(|key| key.field == field) {
+            return Err(ApiError::BadRequest(format!(
+                "Sort field '{}' given more than once",
+                name.trim()
+            )));
+        }
+        keys.push(SortKey { field, direction });
+    }
+
+    Ok(keys)
+}
+
+/// Append one `AND ...` condition per active filter.
+///
+/// The builder must already contain a `WHERE` clause. New filters belong here so
+/// that every query over the filtered book set stays consistent.
+pub(crate) fn push_book_filters(
+    builder: &mut QueryBuilder<'static, Postgres>,
+    filter: &BookFilter,
+) {
+    if let Some(status) = filter.status {
+        builder.push(" AND status = ").push_bind(status);
+    }
+
+    // Full-text matches, plus trigram matches so misspelled names still hit
+    if let Some(terms) = SearchTerms::from_filter(filter) {
+        builder
+            .push(" AND (search_vector @@ to_tsquery('english', ")
+            .push_bind(terms.tsquery)
+            .push(") OR ")
+            .push_bind(terms.raw.clone())
+            .push(" <% title OR ")
+            .push_bind(terms.raw)
+            .push(" <% author)");
+    }
+
+    // `tag_paths` holds each tag and its ancestors, so `fiction` also finds
+    // `fiction/sci-fi` through the GIN index
+    let tags = filter.tag_list();
+    if !tags.is_empty() {
+        match filter.tag_match.unwrap_or_default() {
+            TagMatch::Any => builder.push(" AND tag_paths && ").push_bind(tags),
+            TagMatch::All => builder.push(" AND tag_paths @> ").push_bind(tags),
+        };
+    }
+
+    if let Some(author) = filter.author.as_deref().map(str::trim) {
+        if !author.is_empty() {
+            builder
+                .push(" AND lower(author) = lower(")
+                .push_bind(author.to_string())
+                .push(")");
+        }
+    }
+
+    if let Some(min_rating) = filter.min_rating {
+        builder.push(" AND rating >= ").push_bind(min_rating);
+    }
+    if let Some(max_rating) = filter.max_rating {
+        builder.push(" AND rating <= ").push_bind(max_rating);
+    }
+
+    if let Some(added_from) = filter.added_from {
+        builder.push(" AND date_added >= ").push_bind(added_from);
+    }
+    if let Some(added_to) = filter.added_to {
+        builder.push(" AND date_added <= ").push_bind(added_to);
+    }
+    if let Some(finished_from) = filter.finished_from {
+        builder
+            .push(" AND date_finished >= ")
+            .push_bind(finished_from);
+    }
+    if let Some(finished_to) = filter.finished_to {
+        builder
+            .push(" AND date_finished <= ")
+            .push_bind(finished_to);
+    }
+
+    match filter.has_cover {
+        Some(true) => builder.push(" AND cover_url IS NOT NULL AND cover_url <> ''"),
+        Some(false) => builder.push(" AND (cover_url IS NULL OR cover_url = '')"),
+        None => builder,
+    };
+
+    match filter.has_notes {
+        Some(true) => builder.push(" AND notes_count > 0"),
+        Some(false) => builder.push(" AND notes_count = 0"),
+        None => builder,
+    };
+
+    if let Some(collection) = filter.collection {
+        builder
+            .push(" AND id IN (SELECT book_id FROM collection_books WHERE collection_id = ")
+            .push_bind(collection)
+            .push(")");
+    }
+}
+
+/// Reject filters whose ranges can never match anything
+pub(crate) fn validate_filter(filter: &BookFilter) -> ApiResult<()> {
+    for rating in [filter.min_rating, filter.max_rating].into_iter().flatten() {
+        if !(1..=5).contains(&rating) {
+            return Err(ApiError::BadRequest(
+                "Rating filters must be between 1 and 5".to_string(),
+            ));
+        }
+    }
+
+    if let (Some(min), Some(max)) = (filter.min_rating, filter.max_rating) {
+        if min > max {
+            return Err(ApiError::BadRequest(
+                "min_rating must not be greater than max_rating".to_string(),
+            ));
+        }
+    }
+
+    if let (Some(from), Some(to)) = (filter.added_from, filter.added_to) {
+        if from > to {
+            return Err(ApiError::BadRequest(
+                "added_from must not be after added_to".to_string(),
+            ));
+        }
+    }
+
+    if let (Some(from), Some(to)) = (filter.finished_from, filter.finished_to) {
+        if from > to {
+            return Err(ApiError::BadRequest(
+                "finished_from must not be after finished_to".to_string(),
+            ));
+        }
+    }
+
+    // A blank search lists everything, but one made only of punctuation would
+    // otherwise be dropped silently
+    if let Some(search) = filter.search.as_deref().map(str::trim) {
+        if !search.is_empty() && prefix_tsquery(search).is_none() {
+            return Err(ApiError::BadRequest(
+                "Search query must contain at least one word".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Turn free text into a `to_tsquery` expression that prefix-matches every word.
+///
+/// Only letters and digits survive, so the result can never be a tsquery syntax
+/// error. Returns `None` when nothing searchable is left.
+pub(crate) fn prefix_tsquery(search: &str) -> Option<String> {
+    let terms: Vec<String> = search
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("{}:*", word.to_lowercase()))
+        .collect();
+
+    (!terms.is_empty()).then(|| terms.join(" & "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter_with_search(search: &str) -> BookFilter {
+        BookFilter {
+            search: Some(search.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn hostile_search_is_bound_not_interpolated() {
+        let hostile = [
+            "'; DROP TABLE books; --",
+            "O'Brien",
+            "\" OR 1=1 --",
+            "%' OR '1'='1",
+            "\\'; SELECT pg_sleep(10); --",
+        ];
+
+        for search in hostile {
+            let builder = book_list_query(&filter_with_search(search), None, None, 50, 0);
+            let sql = builder.sql();
+
+            assert!(!sql.contains(search), "search leaked into SQL: {}", sql);
+            assert!(!sql.contains("DROP"), "search leaked into SQL: {}", sql);
+            assert!(
+                sql.contains("AND (search_vector @@ to_tsquery('english', $4) OR $5 <% title OR $6 <% author)"),
+                "unexpected SQL: {}",
+                sql
+            );
+        }
+    }
+
+    #[test]
+    fn pagination_is_bound() {
+        let builder = book_list_query(&filter_with_search("dune"), None, None, 25, 50);
+        assert!(builder.sql().ends_with("LIMIT $7 OFFSET $8"));
+    }
+
+    #[test]
+    fn search_orders_exact_matches_before_fuzzy_ones() {
+        let builder = book_list_query(&filter_with_search("dostoyevsky"), None, None, 50, 0);
+        assert!(builder
+            .sql()
+            .contains("ORDER BY exact_match DESC, similarity DESC"));
+    }
+
+    #[test]
+    fn blank_search_adds_no_condition() {
+        for search in ["   ", "'; --", "&|!"] {
+            let builder = book_list_query(&filter_with_search(search), None, None, 50, 0);
+            assert!(!builder.sql().contains("search_vector"));
+        }
+    }
+
+    #[test]
+    fn tsquery_keeps_only_words() {
+        assert_eq!(prefix_tsquery("Dune"), Some("dune:*".to_string()));
+        assert_eq!(
+            prefix_tsquery("O'Brien & co"),
+            Some("o:* & brien:* & co:*".to_string())
+        );
+        assert_eq!(
+            prefix_tsquery("'; DROP TABLE books; --"),
+            Some("drop:* & table:* & books:*".to_string())
+        );
+        assert_eq!(prefix_tsquery("!:*|&"), None);
+    }
+
+    #[test]
+    fn tag_filter_matches_tags_and_their_children() {
+        let any = BookFilter {
+            tags: Some("sci-fi, classics,,".to_string()),
+            ..Default::default()
+        };
+        assert!(book_list_query(&any, None, None, 50, 0)
+            .sql()
+            .contains("WHERE 1=1 AND tag_paths && $1"));
+
+        let all = BookFilter {
+            tag_match: Some(TagMatch::All),
+            ..any
+        };
+        assert!(book_list_query(&all, None, None, 50, 0)
+            .sql()
+            .contains("WHERE 1=1 AND tag_paths @> $1"));
+        assert_eq!(all.tag_list(), vec!["sci-fi", "classics"]);
+    }
+
+    #[test]
+    fn count_query_checks_every_match_for_an_exact_hit() {
+        let search = BookFilter {
+            search: Some("dune".to_string()),
+            status: Some(crate::models::BookStatus::Reading),
+            ..Default::default()
+        };
+        let sql = book_count_query(&search).sql().to_string();
+        assert!(sql.starts_with(
+            "SELECT COUNT(*) AS total, COALESCE(bool_or(search_vector @@ to_tsquery('english', $1)), FALSE) AS has_exact_match FROM books WHERE 1=1"
+        ));
+        assert!(sql.contains(" AND status = "));
+
+        let browse = BookFilter::default();
+        assert_eq!(
+            book_count_query(&browse).sql(),
+            "SELECT COUNT(*) AS total, TRUE AS has_exact_match FROM books WHERE 1=1"
+        );
+    }
+
+    #[test]
+    fn inverted_ranges_are_rejected() {
+        let ratings = BookFilter {
+            min_rating: Some(4),
+            max_rating: Some(2),
+            ..Default::default()
+        };
+        assert!(validate_filter(&ratings).is_err());
+
+        let out_of_scale = BookFilter {
+            min_rating: Some(0),
+            ..Default::default()
+        };
+        assert!(validate_filter(&out_of_scale).is_err());
+
+        let dates = BookFilter {
+            added_from: chrono::NaiveDate::from_ymd_opt(2025, 2, 1),
+            added_to: chrono::NaiveDate::from_ymd_opt(2025, 1, 1),
+            ..Default::default()
+        };
+        assert!(validate_filter(&dates).is_err());
+    }
+
+    #[test]
+    fn search_without_words_is_rejected() {
+        assert!(validate_filter(&filter_with_search("'")).is_err());
+        assert!(validate_filter(&filter_with_search("&|!")).is_err());
+        assert!(validate_filter(&filter_with_search("   ")).is_ok());
+        assert!(validate_filter(&filter_with_search("dune")).is_ok());
+    }
+
+    #[test]
+    fn sort_keys_are_whitelisted() {
+        let keys = parse_sort(Some("rating:desc, title")).unwrap();
+        let builder = book_list_query(&BookFilter::default(), Some(&keys), None, 50, 0);
+        assert!(builder.sql().contains(
+            "ORDER BY rating DESC NULLS LAST, lower(title) ASC NULLS LAST, id ASC LIMIT"
+        ));
+
+        assert!(parse_sort(Some("title; DROP TABLE books")).is_err());
+        assert!(parse_sort(Some("rating:sideways")).is_err());
+        assert!(parse_sort(Some("rating,rating:desc")).is_err());
+        assert!(parse_sort(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn default_order_is_newest_first() {
+        let keys = effective_sort(Vec::new(), false).unwrap();
+        let builder = book_list_query(&BookFilter::default(), Some(&keys), None, 50, 0);
+        assert!(builder
+            .sql()
+            .contains("ORDER BY date_added DESC NULLS LAST, id DESC LIMIT"));
+    }
+
+    fn sample_book() -> Book {
+        Book {
+            id: 42,
+            title: "Dune".to_string(),
+            author: "Frank Herbert".to_string(),
+            cover_url: None,
+            tags: Vec::new(),
+            status: crate::models::BookStatus::Finished,
+            date_added: NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            date_started: None,
+            date_finished: None,
+            rating: Some(5),
+            description: None,
+            notes_count: 0,
+            isbn: None,
+            isbn13: None,
+            page_count: None,
+            audio_minutes: None,
+            abandon_reason: None,
+            abandon_page: None,
+        }
+    }
+
+    #[test]
+    fn cursor_round_trips_and_is_tied_to_its_sort() {
+        let keys = parse_sort(Some("rating:desc,date_finished")).unwrap();
+        let cursor = Cursor::after(&sample_book(), &keys);
+        let token = cursor.encode(&keys);
+
+        assert_eq!(Cursor::decode(&token, &keys).unwrap(), cursor);
+
+        let other = parse_sort(Some("rating")).unwrap();
+        assert!(Cursor::decode(&token, &other).is_err());
+        assert!(Cursor::decode("not a cursor", &keys).is_err());
+    }
+
+    #[test]
+    fn cursor_continues_after_last_row() {
+        let keys = parse_sort(Some("rating:desc,date_finished")).unwrap();
+        let cursor = Cursor::after(&sample_book(), &keys);
+        let builder = book_list_query(&BookFilter::default(), Some(&keys), Some(&cursor), 50, 0);
+
+        assert!(builder.sql().contains(
+            "AND (((rating < $1 OR rating IS NULL)) OR (rating = $2 AND FALSE) \
+             OR (rating = $3 AND date_finished IS NULL AND id > $4)) ORDER BY"
+        ));
+    }
+
+    #[test]
+    fn relevance_order_has_no_cursor() {
+        assert!(effective_sort(Vec::new(), true).is_none());
+        assert!(effective_sort(parse_sort(Some("title")).unwrap(), true).is_some());
+    }
+
+    #[test]
+    fn facets_ignore_their_own_filter() {
+        let filter = BookFilter {
+            status: Some(crate::models::BookStatus::Reading),
+            min_rating: Some(4),
+            ..Default::default()
+        };
+
+        let status = facet_query(&filter, Facet::Status);
+        assert!(!status.sql().contains("status ="));
+        assert!(status.sql().contains("AND rating >= $1 GROUP BY value"));
+        assert!(status.sql().contains("WHERE 1=1 AND status IS NOT NULL"));
+
+        let ratings = facet_query(&filter, Facet::Rating);
+        assert!(ratings.sql().contains("AND status = $1 GROUP BY value"));
+    }
+}
@@ -1,54 +1,103 @@
-use chrono::Utc;
-use sqlx::{PgPool, Row};
+use chrono::{NaiveDate, Utc};
+use sqlx::{PgPool, Postgres, Row};
 use validator::Validate;
 
-use crate::errors::ApiResult;
-use crate::models::{Book, BookFilter, BookStatus, CreateBookRequest, UpdateBookRequest};
+use super::book_query::{
+    book_count_query, book_list_query, effective_sort, facet_query, parse_sort, validate_filter,
+    Cursor, Facet, SearchTerms,
+};
+use super::read_through_service::sync_read_throughs;
+use super::tag_normalizer::TagNormalizer;
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{
+    Book, BookFacets, BookFilter, BookList, BookListItem, BookStatus, CreateBookRequest,
+    FacetCount, UpdateBookRequest,
+};
 
 #[derive(Clone)]
 pub struct BookService {
     pool: PgPool,
+    tags: TagNormalizer,
 }
 
 impl BookService {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            tags: TagNormalizer::default(),
+        }
+    }
+
+    /// Normalize tags with `tags` instead of the alias-free default
+    pub fn with_tag_normalizer(mut self, tags: TagNormalizer) -> Self {
+        self.tags = tags;
+        self
     }
 
     pub async fn create_book(&self, request: CreateBookRequest) -> ApiResult<Book> {
         // Validate the request using the validator crate
         request.validate()?;
 
-        let status = request.status.unwrap_or(BookStatus::Wishlist);
-        let tags = request.tags.unwrap_or_default();
+        let state = ReadingState::default().transition(
+            request.status,
+            request.date_started,
+            request.date_finished,
+        )?;
+        let abandonment = Abandonment::default().apply(
+            Abandonment::requested(request.abandon_reason, request.abandon_page),
+            state.status,
+            request.page_count,
+        )?;
+        let tags = self.tags.normalize_all(&request.tags.unwrap_or_default());
         let date_added = Utc::now().date_naive();
 
+        let mut tx = self.pool.begin().await?;
+
         let row = sqlx::query(
             r#"
-            INSERT INTO books (title, author, cover_url, tags, status, date_added, date_finished, rating, description, notes_count)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, 0)
-            RETURNING id, title, author, cover_url, tags, status::text, date_added, date_finished, rating, description, notes_count
+            INSERT INTO books (title, author, cover_url, tags, status, date_added, date_started, date_finished, rating, description, notes_count, isbn, isbn13, page_count, audio_minutes, abandon_reason, abandon_page)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, 0, $11, $12, $13, $14, $15, $16)
+            RETURNING id
             "#,
         )
         .bind(request.title.trim())
         .bind(request.author.trim())
         .bind(request.cover_url)
         .bind(&tags)
-        .bind(status as BookStatus)
+        .bind(state.status as BookStatus)
         .bind(date_added)
-        .bind(request.date_finished)
+        .bind(state.date_started)
+        .bind(state.date_finished)
         .bind(request.rating)
         .bind(request.description)
-        .fetch_one(&self.pool)
+        .bind(request.isbn)
+        .bind(request.isbn13)
+        .bind(request.page_count)
+        .bind(request.audio_minutes)
+        .bind(abandonment.reason)
+        .bind(abandonment.page)
+        .fetch_one(&mut *tx)
         .await?;
+        let id = row.get("id");
 
-        Ok(self.row_to_book(&row))
+        sync_read_throughs(
+            &mut tx,
+            id,
+            Some(state.status),
+            state.date_finished,
+            state.date_started,
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        self.get_book_by_id(id).await
     }
 
     pub async fn get_book_by_id(&self, id: i32) -> ApiResult<Book> {
         let row = sqlx::query(
             r#"
-            SELECT id, title, author, cover_url, tags, status::text, date_added, date_finished, rating, description, notes_count
+            SELECT id, title, author, cover_url, tags, status::text, date_added, date_started, date_finished, rating, description, notes_count, isbn, isbn13, page_count, audio_minutes, abandon_reason, abandon_page
             FROM books
             WHERE id = $1
             "#,
@@ -60,45 +109,179 @@ impl BookService {
         Ok(self.row_to_book(&row))
     }
 
-    pub async fn get_all_books(&self, filter: BookFilter) -> ApiResult<Vec<Book>> {
-        let limit = filter.limit.unwrap_or(50).min(100) as i64;
-        let offset = (filter.page.unwrap_or(1) - 1) * limit as u32;
-
-        let mut query = String::from(
-            "SELECT id, title, author, cover_url, tags, status::text, date_added, date_finished, rating, description, notes_count FROM books WHERE 1=1"
-        );
-        let mut conditions = Vec::new();
+    pub async fn get_all_books(&self, mut filter: BookFilter) -> ApiResult<BookList> {
+        validate_filter(&filter)?;
+        if filter.tags.is_some() {
+            filter.tags = Some(self.tags.normalize_all(&filter.tag_list()).join(","));
+        }
+        let search = SearchTerms::from_filter(&filter);
+        let sort = effective_sort(parse_sort(filter.sort.as_deref())?, search.is_some());
 
-        if let Some(status) = &filter.status {
-            conditions.push(format!(
-                " AND status = '{}'",
-                match status {
-                    BookStatus::Reading => "reading",
-                    BookStatus::Finished => "finished",
-                    BookStatus::Wishlist => "wishlist",
-                }
-            ));
+        let limit = filter.limit.unwrap_or(50).clamp(1, 100);
+        let page = filter.page.unwrap_or(1);
+        if page == 0 {
+            return Err(ApiError::BadRequest("page must be at least 1".to_string()));
         }
 
-        if let Some(search) = &filter.search {
-            if !search.trim().is_empty() {
-                conditions.push(format!(
-                    " AND (title ILIKE '%{}%' OR author ILIKE '%{}%')",
-                    search, search
-                ));
+        let cursor = match (filter.cursor.as_deref(), sort.as_deref()) {
+            (None, _) => None,
+            (Some(token), Some(keys)) => Some(Cursor::decode(token, keys)?),
+            (Some(_), None) => {
+                return Err(ApiError::BadRequest(
+                    "Cursors require an explicit sort when searching".to_string(),
+                ))
             }
-        }
+        };
+        // A cursor replaces the page offset
+        let offset = match cursor {
+            Some(_) => 0,
+            None => (page as i64 - 1) * limit as i64,
+        };
 
-        for condition in conditions {
-            query.push_str(&condition);
-        }
+        // Fetch one extra row to learn whether another page follows
+        let mut rows = book_list_query(
+            &filter,
+            sort.as_deref(),
+            cursor.as_ref(),
+            limit as i64 + 1,
+            offset,
+        )
+        .build()
+        .fetch_all(&self.pool)
+        .await?;
+        let has_more = rows.len() > limit as usize;
+        rows.truncate(limit as usize);
+
+        let counts = book_count_query(&filter)
+            .build()
+            .fetch_one(&self.pool)
+            .await?;
+        let total: i64 = counts.get("total");
+
+        // Judged over every match, not just this page
+        let suggestion = match search {
+            Some(terms) if !counts.get::<bool, _>("has_exact_match") => {
+                self.suggest(&terms.raw).await?
+            }
+            _ => None,
+        };
 
-        query.push_str(&format!(
-            " ORDER BY date_added DESC LIMIT {} OFFSET {}",
-            limit, offset
-        ));
+        let items: Vec<BookListItem> = rows
+            .iter()
+            .map(|row| BookListItem {
+                book: self.row_to_book(row),
+                similarity: row.get("similarity"),
+            })
+            .collect();
+
+        let next_cursor = match (sort.as_deref(), items.last()) {
+            (Some(keys), Some(last)) if has_more => {
+                Some(Cursor::after(&last.book, keys).encode(keys))
+            }
+            _ => None,
+        };
+
+        let facets = match filter.facets {
+            Some(true) => Some(self.facets(&filter).await?),
+            _ => None,
+        };
+
+        Ok(BookList {
+            items,
+            total,
+            page: cursor.is_none().then_some(page),
+            limit,
+            next_cursor,
+            suggestion,
+            facets,
+        })
+    }
+
+    /// Per-value counts for each sidebar facet
+    async fn facets(&self, filter: &BookFilter) -> ApiResult<BookFacets> {
+        Ok(BookFacets {
+            status: self.facet_counts(filter, Facet::Status).await?,
+            tags: self.facet_counts(filter, Facet::Tag).await?,
+            ratings: self.facet_counts(filter, Facet::Rating).await?,
+            finished_years: self.facet_counts(filter, Facet::FinishedYear).await?,
+        })
+    }
+
+    async fn facet_counts<T>(
+        &self,
+        filter: &BookFilter,
+        facet: Facet,
+    ) -> ApiResult<Vec<FacetCount<T>>>
+    where
+        T: for<'r> sqlx::Decode<'r, Postgres> + sqlx::Type<Postgres>,
+    {
+        let rows = facet_query(filter, facet)
+            .build()
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(FacetCount {
+                    value: row.try_get("value")?,
+                    count: row.try_get("count")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Closest title or author to a search that found no exact matches
+    async fn suggest(&self, search: &str) -> ApiResult<Option<String>> {
+        let row = sqlx::query(
+            r#"
+            SELECT candidate
+            FROM (
+                SELECT title AS candidate, word_similarity($1, title) AS score FROM books WHERE $1 <% title
+                UNION ALL
+                SELECT author, word_similarity($1, author) FROM books WHERE $1 <% author
+            ) AS candidates
+            ORDER BY score DESC, candidate
+            LIMIT 1
+            "#,
+        )
+        .bind(search)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| row.get("candidate")))
+    }
+
+    /// Fetch every book in the library, oldest first, for exports
+    pub async fn list_all_books(&self) -> ApiResult<Vec<Book>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, title, author, cover_url, tags, status::text, date_added, date_started, date_finished, rating, description, notes_count, isbn, isbn13, page_count, audio_minutes, abandon_reason, abandon_page
+            FROM books
+            ORDER BY id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let books: Vec<Book> = rows.iter().map(|row| self.row_to_book(row)).collect();
+        Ok(books)
+    }
+
+    /// Books in a collection, in the collection's manual order
+    pub async fn get_books_in_collection(&self, collection_id: i32) -> ApiResult<Vec<Book>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, title, author, cover_url, tags, status::text, date_added, date_started, date_finished, rating, description, notes_count, isbn, isbn13, page_count, audio_minutes, abandon_reason, abandon_page
+            FROM books
+            JOIN collection_books ON collection_books.book_id = books.id
+            WHERE collection_books.collection_id = $1
+            ORDER BY collection_books.position, collection_books.added_at, books.id
+            "#,
+        )
+        .bind(collection_id)
+        .fetch_all(&self.pool)
+        .await?;
 
-        let rows = sqlx::query(&query).fetch_all(&self.pool).await?;
         let books: Vec<Book> = rows.iter().map(|row| self.row_to_book(row)).collect();
         Ok(books)
     }
@@ -108,9 +291,30 @@ impl BookService {
         request.validate()?;
 
         // First check if book exists
-        self.get_book_by_id(id).await?;
+        let book = self.get_book_by_id(id).await?;
 
-        let row = sqlx::query(
+        // Only status and date changes are checked, so legacy rows stay editable
+        let reading_changed = request.status.is_some()
+            || request.date_started.is_some()
+            || request.date_finished.is_some();
+        let state = if reading_changed {
+            ReadingState::from(&book).transition(
+                request.status,
+                request.date_started,
+                request.date_finished,
+            )?
+        } else {
+            ReadingState::from(&book)
+        };
+        let abandonment = Abandonment::requested(book.abandon_reason, book.abandon_page).apply(
+            Abandonment::requested(request.abandon_reason, request.abandon_page),
+            state.status,
+            request.page_count.or(book.page_count),
+        )?;
+
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
             r#"
             UPDATE books
             SET
@@ -118,27 +322,53 @@ impl BookService {
                 author = COALESCE($3, author),
                 cover_url = COALESCE($4, cover_url),
                 tags = COALESCE($5, tags),
-                status = COALESCE($6, status),
+                status = $6,
                 rating = COALESCE($7, rating),
                 description = COALESCE($8, description),
-                date_finished = COALESCE($9, date_finished)
+                date_finished = $9,
+                isbn = COALESCE($10, isbn),
+                isbn13 = COALESCE($11, isbn13),
+                page_count = COALESCE($12, page_count),
+                audio_minutes = COALESCE($13, audio_minutes),
+                date_started = $14,
+                abandon_reason = $15,
+                abandon_page = $16
             WHERE id = $1
-            RETURNING id, title, author, cover_url, tags, status::text, date_added, date_finished, rating, description, notes_count
             "#,
         )
         .bind(id)
         .bind(request.title)
         .bind(request.author)
         .bind(request.cover_url)
-        .bind(request.tags.as_deref())
-        .bind(request.status.map(|s| s as BookStatus))
+        .bind(request.tags.map(|tags| self.tags.normalize_all(&tags)))
+        .bind(state.status as BookStatus)
         .bind(request.rating)
         .bind(request.description)
-        .bind(request.date_finished)
-        .fetch_one(&self.pool)
+        .bind(state.date_finished)
+        .bind(request.isbn)
+        .bind(request.isbn13)
+        .bind(request.page_count)
+        .bind(request.audio_minutes)
+        .bind(state.date_started)
+        .bind(abandonment.reason)
+        .bind(abandonment.page)
+        .execute(&mut *tx)
         .await?;
 
-        Ok(self.row_to_book(&row))
+        if reading_changed {
+            sync_read_throughs(
+                &mut tx,
+                id,
+                Some(state.status),
+                state.date_finished,
+                state.date_started,
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        self.get_book_by_id(id).await
     }
 
     pub async fn delete_book(&self, id: i32) -> ApiResult<()> {
@@ -157,7 +387,32 @@ impl BookService {
         Ok(())
     }
 
-    fn row_to_book(&self, row: &sqlx::postgres::PgRow) -> Book {
+    /// Recompute `notes_count` for every book from the notes table.
+    ///
+    /// Counts are normally kept in sync by database triggers; this is a repair
+    /// tool for data that was modified outside of them. Returns the number of
+    /// books whose count was corrected.
+    pub async fn recompute_notes_counts(&self) -> ApiResult<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE books
+            SET notes_count = counts.actual
+            FROM (
+                SELECT books.id, COUNT(notes.id)::INTEGER AS actual
+                FROM books
+                LEFT JOIN notes ON notes.book_id = books.id
+                GROUP BY books.id
+            ) AS counts
+            WHERE books.id = counts.id AND books.notes_count IS DISTINCT FROM counts.actual
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub(crate) fn row_to_book(&self, row: &sqlx::postgres::PgRow) -> Book {
         Book {
             id: row.get("id"),
             title: row.get("title"),
@@ -170,15 +425,179 @@ impl BookService {
                 Some("reading") => BookStatus::Reading,
                 Some("finished") => BookStatus::Finished,
                 Some("wishlist") => BookStatus::Wishlist,
+                Some("paused") => BookStatus::Paused,
+                Some("abandoned") => BookStatus::Abandoned,
                 _ => BookStatus::Wishlist,
             },
             date_added: row
                 .get::<Option<chrono::NaiveDate>, _>("date_added")
                 .unwrap_or_else(|| Utc::now().date_naive()),
+            date_started: row.get("date_started"),
             date_finished: row.get("date_finished"),
             rating: row.get("rating"),
             description: row.get("description"),
             notes_count: row.get::<Option<i32>, _>("notes_count").unwrap_or(0),
+            isbn: row.get("isbn"),
+            isbn13: row.get("isbn13"),
+            page_count: row.get("page_count"),
+            audio_minutes: row.get("audio_minutes"),
+            abandon_reason: row.get("abandon_reason"),
+            abandon_page: row.get("abandon_page"),
+        }
+    }
+}
+
+/// A book's status together with the dates that must agree with it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ReadingState {
+    pub(crate) status: BookStatus,
+    pub(crate) date_started: Option<NaiveDate>,
+    pub(crate) date_finished: Option<NaiveDate>,
+}
+
+impl Default for ReadingState {
+    /// New books start out on the wishlist
+    fn default() -> Self {
+        Self {
+            status: BookStatus::Wishlist,
+            date_started: None,
+            date_finished: None,
+        }
+    }
+}
+
+impl From<&Book> for ReadingState {
+    fn from(book: &Book) -> Self {
+        Self {
+            status: book.status,
+            date_started: book.date_started,
+            date_finished: book.date_finished,
+        }
+    }
+}
+
+impl ReadingState {
+    /// Apply a requested status and dates, stamping today on transitions that
+    /// start or finish a read, and reject combinations that contradict each other.
+    ///
+    /// Dates given explicitly always win over the stamped ones.
+    pub(crate) fn transition(
+        self,
+        status: Option<BookStatus>,
+        date_started: Option<NaiveDate>,
+        date_finished: Option<NaiveDate>,
+    ) -> ApiResult<Self> {
+        let today = Utc::now().date_naive();
+        let target = status.unwrap_or(self.status);
+        let mut next = Self {
+            status: target,
+            date_started: date_started.or(self.date_started),
+            date_finished: date_finished.or(self.date_finished),
+        };
+
+        if target != self.status {
+            match target {
+                // Starting a read, or a re-read, always begins a new start date
+                // Pausing, resuming or giving up on a read keeps its start date
+                _ if target.has_open_read() && self.status.has_open_read() => {}
+                BookStatus::Reading | BookStatus::Paused | BookStatus::Abandoned => {
+                    next.date_started = Some(date_started.unwrap_or(today))
+                }
+                BookStatus::Finished => next.date_finished = Some(date_finished.unwrap_or(today)),
+                BookStatus::Wishlist => {
+                    if self.date_finished.is_some() {
+                        return Err(ApiError::ValidationError(
+                            "A book that has been finished cannot move back to the wishlist"
+                                .to_string(),
+                        ));
+                    }
+                    next.date_started = date_started;
+                }
+            }
         }
+
+        next.validate()?;
+        Ok(next)
+    }
+
+    fn validate(&self) -> ApiResult<()> {
+        let (started_after_finish, finished_after_start) =
+            match (self.date_started, self.date_finished) {
+                (Some(started), Some(finished)) => (started > finished, finished > started),
+                _ => (false, false),
+            };
+
+        let problem = match self.status {
+            BookStatus::Wishlist if self.date_started.is_some() || self.date_finished.is_some() => {
+                Some("A wishlist book cannot have a start or finish date")
+            }
+            BookStatus::Finished if self.date_finished.is_none() => {
+                Some("A finished book must have a finish date")
+            }
+            BookStatus::Finished if started_after_finish => {
+                Some("date_started must not be after date_finished")
+            }
+            // An earlier finish belongs to a previous read; a later one means this read is over
+            status if status.has_open_read() && finished_after_start => {
+                Some("A book still being read cannot have finished after it was started")
+            }
+            _ => None,
+        };
+
+        match problem {
+            Some(message) => Err(ApiError::ValidationError(message.to_string())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Why and where an abandoned book was given up
+#[derive(Debug, Default)]
+struct Abandonment {
+    reason: Option<String>,
+    page: Option<i32>,
+}
+
+impl Abandonment {
+    fn requested(reason: Option<String>, page: Option<i32>) -> Self {
+        Self {
+            reason: reason
+                .map(|reason| reason.trim().to_string())
+                .filter(|reason| !reason.is_empty()),
+            page,
+        }
+    }
+
+    /// Merge `requested` into the current details while the book is abandoned;
+    /// any other status drops them and rejects new ones.
+    fn apply(
+        self,
+        requested: Abandonment,
+        status: BookStatus,
+        page_count: Option<i32>,
+    ) -> ApiResult<Self> {
+        if status != BookStatus::Abandoned {
+            if requested.reason.is_some() || requested.page.is_some() {
+                return Err(ApiError::ValidationError(
+                    "abandon_reason and abandon_page only apply to abandoned books".to_string(),
+                ));
+            }
+            return Ok(Self::default());
+        }
+
+        let merged = Self {
+            reason: requested.reason.or(self.reason),
+            page: requested.page.or(self.page),
+        };
+        if let (Some(page), Some(page_count)) = (merged.page, page_count) {
+            if page > page_count {
+                return Err(ApiError::ValidationError(format!(
+                    "abandon_page {} is past the book's {} pages",
+                    page, page_count
+                )));
+            }
+        }
+
+        Ok(merged)
     }
 }
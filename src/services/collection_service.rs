@@ -0,0 +1,331 @@
+use sqlx::{PgPool, Postgres, Row, Transaction};
+use validator::Validate;
+
+use super::{ensure_book_exists, is_unique_violation, BookService};
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{
+    AddCollectionBookRequest, Book, Collection, CreateCollectionRequest, ReorderRequest,
+    UpdateCollectionRequest,
+};
+
+/// Columns selected for a collection, including its member count
+const COLLECTION_COLUMNS: &str = "id, name, description, position, created_at, updated_at, \
+     (SELECT COUNT(*) FROM collection_books cb WHERE cb.collection_id = collections.id) AS book_count";
+
+#[derive(Clone)]
+pub struct CollectionService {
+    pool: PgPool,
+    book_service: BookService,
+}
+
+impl CollectionService {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            book_service: BookService::new(pool.clone()),
+            pool,
+        }
+    }
+
+    /// All collections in their manual order
+    pub async fn get_all_collections(&self) -> ApiResult<Vec<Collection>> {
+        let query = format!(
+            "SELECT {} FROM collections ORDER BY position, id",
+            COLLECTION_COLUMNS
+        );
+        let rows = sqlx::query(&query).fetch_all(&self.pool).await?;
+
+        Ok(rows.iter().map(row_to_collection).collect())
+    }
+
+    pub async fn get_collection_by_id(&self, id: i32) -> ApiResult<Collection> {
+        let query = format!(
+            "SELECT {} FROM collections WHERE id = $1",
+            COLLECTION_COLUMNS
+        );
+        let row = sqlx::query(&query)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| collection_not_found(id))?;
+
+        Ok(row_to_collection(&row))
+    }
+
+    /// Create a collection at the end of the list
+    pub async fn create_collection(
+        &self,
+        request: CreateCollectionRequest,
+    ) -> ApiResult<Collection> {
+        // Validate the request using the validator crate
+        request.validate()?;
+
+        let name = trimmed_name(&request.name)?;
+        self.ensure_name_available(name, None).await?;
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO collections (name, description, position)
+            VALUES ($1, $2, (SELECT COALESCE(MAX(position), 0) + 1 FROM collections))
+            RETURNING id
+            "#,
+        )
+        .bind(name)
+        .bind(request.description)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| name_conflict(e, name))?;
+
+        self.get_collection_by_id(row.get("id")).await
+    }
+
+    pub async fn update_collection(
+        &self,
+        id: i32,
+        request: UpdateCollectionRequest,
+    ) -> ApiResult<Collection> {
+        // Validate the request using the validator crate
+        request.validate()?;
+
+        let name = request.name.as_deref().map(trimmed_name).transpose()?;
+        if let Some(name) = name {
+            self.ensure_name_available(name, Some(id)).await?;
+        }
+
+        let result = sqlx::query(
+            r#"
+            UPDATE collections
+            SET
+                name = COALESCE($2, name),
+                description = COALESCE($3, description),
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(name)
+        .bind(request.description)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| name_conflict(e, name.unwrap_or_default()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(collection_not_found(id));
+        }
+
+        self.get_collection_by_id(id).await
+    }
+
+    pub async fn delete_collection(&self, id: i32) -> ApiResult<()> {
+        let result = sqlx::query("DELETE FROM collections WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(collection_not_found(id));
+        }
+
+        Ok(())
+    }
+
+    /// Put collections in the order given by `request.ids`
+    pub async fn reorder_collections(&self, request: ReorderRequest) -> ApiResult<Vec<Collection>> {
+        let mut tx = self.pool.begin().await?;
+
+        let ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM collections FOR UPDATE")
+            .fetch_all(&mut *tx)
+            .await?;
+        ensure_same_members(&ids, &request.ids, "collection")?;
+
+        sqlx::query(
+            r#"
+            UPDATE collections
+            SET position = new_order.position, updated_at = NOW()
+            FROM unnest($1::INTEGER[]) WITH ORDINALITY AS new_order(id, position)
+            WHERE collections.id = new_order.id
+            "#,
+        )
+        .bind(&request.ids)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        self.get_all_collections().await
+    }
+
+    /// Books in a collection, in the collection's order
+    pub async fn get_collection_books(&self, id: i32) -> ApiResult<Vec<Book>> {
+        self.get_collection_by_id(id).await?;
+        self.book_service.get_books_in_collection(id).await
+    }
+
+    /// Append a book to the end of a collection; adding it again is a no-op
+    pub async fn add_book(
+        &self,
+        id: i32,
+        request: AddCollectionBookRequest,
+    ) -> ApiResult<Vec<Book>> {
+        self.get_collection_by_id(id).await?;
+        ensure_book_exists(&self.pool, request.book_id).await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO collection_books (collection_id, book_id, position)
+            VALUES ($1, $2, (SELECT COALESCE(MAX(position), 0) + 1 FROM collection_books WHERE collection_id = $1))
+            ON CONFLICT (collection_id, book_id) DO NOTHING
+            "#,
+        )
+        .bind(id)
+        .bind(request.book_id)
+        .execute(&self.pool)
+        .await?;
+
+        self.touch(id).await?;
+        self.book_service.get_books_in_collection(id).await
+    }
+
+    pub async fn remove_book(&self, id: i32, book_id: i32) -> ApiResult<()> {
+        let result =
+            sqlx::query("DELETE FROM collection_books WHERE collection_id = $1 AND book_id = $2")
+                .bind(id)
+                .bind(book_id)
+                .execute(&self.pool)
+                .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound(format!(
+                "Book with id {} is not in collection {}",
+                book_id, id
+            )));
+        }
+
+        self.touch(id).await
+    }
+
+    /// Put the books of a collection in the order given by `request.ids`
+    pub async fn reorder_books(&self, id: i32, request: ReorderRequest) -> ApiResult<Vec<Book>> {
+        let mut tx = self.pool.begin().await?;
+
+        lock_collection(&mut tx, id).await?;
+        let book_ids: Vec<i32> =
+            sqlx::query_scalar("SELECT book_id FROM collection_books WHERE collection_id = $1")
+                .bind(id)
+                .fetch_all(&mut *tx)
+                .await?;
+        ensure_same_members(&book_ids, &request.ids, "book in the collection")?;
+
+        sqlx::query(
+            r#"
+            UPDATE collection_books
+            SET position = new_order.position
+            FROM unnest($2::INTEGER[]) WITH ORDINALITY AS new_order(book_id, position)
+            WHERE collection_books.collection_id = $1 AND collection_books.book_id = new_order.book_id
+            "#,
+        )
+        .bind(id)
+        .bind(&request.ids)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("UPDATE collections SET updated_at = NOW() WHERE id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        self.book_service.get_books_in_collection(id).await
+    }
+
+    /// Collection names are unique regardless of case
+    async fn ensure_name_available(&self, name: &str, except_id: Option<i32>) -> ApiResult<()> {
+        let taken = sqlx::query(
+            "SELECT 1 FROM collections WHERE lower(name) = lower($1) AND id IS DISTINCT FROM $2",
+        )
+        .bind(name)
+        .bind(except_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match taken {
+            Some(_) => Err(name_taken(name)),
+            None => Ok(()),
+        }
+    }
+
+    async fn touch(&self, id: i32) -> ApiResult<()> {
+        sqlx::query("UPDATE collections SET updated_at = NOW() WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Lock a collection row for the rest of the transaction, or fail if missing
+async fn lock_collection(tx: &mut Transaction<'_, Postgres>, id: i32) -> ApiResult<()> {
+    sqlx::query("SELECT 1 FROM collections WHERE id = $1 FOR UPDATE")
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or_else(|| collection_not_found(id))?;
+    Ok(())
+}
+
+/// A reorder must name every current member exactly once
+fn ensure_same_members(current: &[i32], requested: &[i32], member: &str) -> ApiResult<()> {
+    let mut current = current.to_vec();
+    let mut requested = requested.to_vec();
+    current.sort_unstable();
+    requested.sort_unstable();
+
+    if current != requested {
+        return Err(ApiError::BadRequest(format!(
+            "ids must list every {} exactly once",
+            member
+        )));
+    }
+
+    Ok(())
+}
+
+fn collection_not_found(id: i32) -> ApiError {
+    ApiError::NotFound(format!("Collection with id {} not found", id))
+}
+
+fn row_to_collection(row: &sqlx::postgres::PgRow) -> Collection {
+    Collection {
+        id: row.get("id"),
+        name: row.get("name"),
+        description: row.get("description"),
+        position: row.get("position"),
+        book_count: row.get("book_count"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+/// Names are stored trimmed, so one made only of whitespace is blank
+fn trimmed_name(name: &str) -> ApiResult<&str> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ApiError::ValidationError(
+            "name: must not be blank".to_string(),
+        ));
+    }
+    Ok(name)
+}
+
+fn name_taken(name: &str) -> ApiError {
+    ApiError::BadRequest(format!("A collection named '{}' already exists", name))
+}
+
+/// A concurrent create or rename can take the name after it was checked
+fn name_conflict(error: sqlx::Error, name: &str) -> ApiError {
+    if is_unique_violation(&error) {
+        name_taken(name)
+    } else {
+        error.into()
+    }
+}
@@ -0,0 +1,204 @@
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, BoxStream, Stream};
+use futures_util::TryStreamExt;
+use serde::Serialize;
+use sqlx::postgres::PgRow;
+use sqlx::{PgConnection, PgPool, Row};
+use tokio::sync::mpsc;
+
+use super::book_query::BOOK_COLUMNS;
+use super::read_through_service::row_to_read_through;
+use super::{BookService, HighlightService, NoteService, ReadThroughService};
+use crate::errors::{ApiError, ApiResult};
+use crate::exporters::markdown::write_markdown_zip;
+use crate::importers::backup::{BACKUP_FORMAT, BACKUP_VERSION};
+use crate::importers::storygraph::write_storygraph_csv;
+use crate::models::{BackupHighlight, BackupNote};
+
+/// Chunks of the backup document buffered ahead of a slow client
+const BACKUP_BUFFER_CHUNKS: usize = 16;
+
+/// Sends backup chunks to the response body
+type ChunkSender = mpsc::Sender<ApiResult<String>>;
+
+#[derive(Clone)]
+pub struct ExportService {
+    pool: PgPool,
+    book_service: BookService,
+    note_service: NoteService,
+    highlight_service: HighlightService,
+    read_through_service: ReadThroughService,
+}
+
+impl ExportService {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool: pool.clone(),
+            book_service: BookService::new(pool.clone()),
+            note_service: NoteService::new(pool.clone()),
+            highlight_service: HighlightService::new(pool.clone()),
+            read_through_service: ReadThroughService::new(pool),
+        }
+    }
+
+    /// Export the whole library in StoryGraph's CSV layout
+    pub async fn export_storygraph_csv(&self) -> ApiResult<String> {
+        let books = self.book_service.list_all_books().await?;
+        let read_throughs = self.read_through_service.list_all_read_throughs().await?;
+        write_storygraph_csv(&books, &read_throughs).map_err(ApiError::InternalError)
+    }
+
+    /// Stream every book, tag, note, highlight and read-through as a versioned
+    /// backup.
+    ///
+    /// Rows are serialized as they are read from one consistent snapshot, so
+    /// the whole library is never held in memory. A failure part way through
+    /// ends the stream with an error, leaving the client a truncated document.
+    pub fn stream_backup(
+        &self,
+        exported_at: DateTime<Utc>,
+    ) -> impl Stream<Item = ApiResult<String>> + Send + 'static {
+        let (sender, receiver) = mpsc::channel(BACKUP_BUFFER_CHUNKS);
+
+        let service = self.clone();
+        tokio::spawn(async move {
+            if let Err(error) = service.write_backup(exported_at, &sender).await {
+                tracing::error!("Backup export failed: {}", error);
+                let _ = sender.send(Err(error)).await;
+            }
+        });
+
+        stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|chunk| (chunk, receiver))
+        })
+    }
+
+    /// Export every book as a Markdown file with its notes and highlights,
+    /// zipped for dropping into an Obsidian vault
+    pub async fn export_markdown_zip(&self) -> ApiResult<Vec<u8>> {
+        let books = self.book_service.list_all_books().await?;
+        let notes = self.note_service.list_all_notes().await?;
+        let highlights = self.highlight_service.list_all_highlights().await?;
+
+        write_markdown_zip(&books, &notes, &highlights).map_err(ApiError::InternalError)
+    }
+
+    async fn write_backup(
+        &self,
+        exported_at: DateTime<Utc>,
+        sender: &ChunkSender,
+    ) -> ApiResult<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+            .execute(&mut *tx)
+            .await?;
+
+        let tags: Vec<String> =
+            sqlx::query("SELECT DISTINCT unnest(tags) AS tag FROM books ORDER BY tag")
+                .fetch_all(&mut *tx)
+                .await?
+                .iter()
+                .map(|row| row.get("tag"))
+                .collect();
+
+        send(
+            sender,
+            format!(
+                "{{\"format\":{},\"version\":{},\"exported_at\":{},\"tags\":{}",
+                to_json(&BACKUP_FORMAT)?,
+                BACKUP_VERSION,
+                to_json(&exported_at)?,
+                to_json(&tags)?
+            ),
+        )
+        .await?;
+
+        let books = format!("SELECT {} FROM books ORDER BY id", BOOK_COLUMNS);
+        send_array(sender, "books", &mut tx, &books, |row| {
+            self.book_service.row_to_book(row)
+        })
+        .await?;
+
+        send_array(
+            sender,
+            "notes",
+            &mut tx,
+            r#"
+            SELECT id, book_id, content, page_number, chapter, created_at, updated_at, import_key
+            FROM notes
+            ORDER BY book_id, id
+            "#,
+            |row| BackupNote {
+                note: self.note_service.row_to_note(row),
+                import_key: row.get("import_key"),
+            },
+        )
+        .await?;
+
+        send_array(
+            sender,
+            "highlights",
+            &mut tx,
+            r#"
+            SELECT id, book_id, text, page_number, chapter, percent, location_start, location_end, color::text, created_at, updated_at, import_key
+            FROM highlights
+            ORDER BY book_id, id
+            "#,
+            |row| BackupHighlight {
+                highlight: self.highlight_service.row_to_highlight(row),
+                import_key: row.get("import_key"),
+            },
+        )
+        .await?;
+
+        send_array(
+            sender,
+            "read_throughs",
+            &mut tx,
+            r#"
+            SELECT id, book_id, started_on, finished_on, rating, format, created_at, updated_at
+            FROM read_throughs
+            ORDER BY book_id, id
+            "#,
+            row_to_read_through,
+        )
+        .await?;
+
+        send(sender, "}".to_string()).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
+
+/// Write `,"name":[...]` with one chunk per row returned by `query`
+async fn send_array<T: Serialize>(
+    sender: &ChunkSender,
+    name: &str,
+    connection: &mut PgConnection,
+    query: &str,
+    to_item: impl Fn(&PgRow) -> T,
+) -> ApiResult<()> {
+    send(sender, format!(",{}:[", to_json(&name)?)).await?;
+
+    let mut rows: BoxStream<'_, Result<PgRow, sqlx::Error>> = sqlx::query(query).fetch(connection);
+    let mut separator = "";
+    while let Some(row) = rows.try_next().await? {
+        send(sender, format!("{}{}", separator, to_json(&to_item(&row))?)).await?;
+        separator = ",";
+    }
+
+    send(sender, "]".to_string()).await
+}
+
+async fn send(sender: &ChunkSender, chunk: String) -> ApiResult<()> {
+    sender
+        .send(Ok(chunk))
+        .await
+        .map_err(|_| ApiError::InternalError("Backup download was cancelled".to_string()))
+}
+
+fn to_json<T: Serialize + ?Sized>(value: &T) -> ApiResult<String> {
+    serde_json::to_string(value)
+        .map_err(|e| ApiError::InternalError(format!("Failed to serialize backup: {}", e)))
+}
@@ -0,0 +1,374 @@
+use chrono::{Months, NaiveDate, Utc};
+use sqlx::{PgPool, Row};
+use validator::Validate;
+
+use super::is_unique_violation;
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{
+    CreateGoalRequest, Goal, GoalFilter, GoalMetric, GoalPeriod, GoalProgress, UpdateGoalRequest,
+};
+
+const GOAL_COLUMNS: &str = "id, period, metric, year, month, target, created_at, updated_at";
+
+#[derive(Clone)]
+pub struct GoalService {
+    pool: PgPool,
+}
+
+impl GoalService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Goals with their progress, latest period first
+    pub async fn get_goals(&self, filter: GoalFilter) -> ApiResult<Vec<GoalProgress>> {
+        let query = format!(
+            "SELECT {} FROM goals WHERE ($1::INTEGER IS NULL OR year = $1) \
+             ORDER BY year DESC, month DESC NULLS FIRST, metric",
+            GOAL_COLUMNS
+        );
+        let rows = sqlx::query(&query)
+            .bind(filter.year)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut goals = Vec::with_capacity(rows.len());
+        for row in &rows {
+            goals.push(self.with_progress(row_to_goal(row)).await?);
+        }
+        Ok(goals)
+    }
+
+    pub async fn get_goal_by_id(&self, id: i32) -> ApiResult<GoalProgress> {
+        let goal = self.find_goal(id).await?;
+        self.with_progress(goal).await
+    }
+
+    pub async fn create_goal(&self, request: CreateGoalRequest) -> ApiResult<GoalProgress> {
+        // Validate the request using the validator crate
+        request.validate()?;
+        match (request.period, request.month) {
+            (GoalPeriod::Monthly, None) => {
+                return Err(ApiError::ValidationError(
+                    "month: required for a monthly goal".to_string(),
+                ))
+            }
+            (GoalPeriod::Yearly, Some(_)) => {
+                return Err(ApiError::ValidationError(
+                    "month: only applies to monthly goals".to_string(),
+                ))
+            }
+            _ => {}
+        }
+        self.ensure_period_available(&request).await?;
+
+        let query = format!(
+            r#"
+            INSERT INTO goals (period, metric, year, month, target)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING {}
+            "#,
+            GOAL_COLUMNS
+        );
+        let row = sqlx::query(&query)
+            .bind(request.period)
+            .bind(request.metric)
+            .bind(request.year)
+            .bind(request.month)
+            .bind(request.target)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| {
+                if is_unique_violation(&e) {
+                    period_taken(&request)
+                } else {
+                    e.into()
+                }
+            })?;
+
+        self.with_progress(row_to_goal(&row)).await
+    }
+
+    pub async fn update_goal(
+        &self,
+        id: i32,
+        request: UpdateGoalRequest,
+    ) -> ApiResult<GoalProgress> {
+        // Validate the request using the validator crate
+        request.validate()?;
+
+        // First check if goal exists
+        self.find_goal(id).await?;
+
+        let query = format!(
+            r#"
+            UPDATE goals
+            SET target = COALESCE($2, target), updated_at = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            GOAL_COLUMNS
+        );
+        let row = sqlx::query(&query)
+            .bind(id)
+            .bind(request.target)
+            .fetch_one(&self.pool)
+            .await?;
+
+        self.with_progress(row_to_goal(&row)).await
+    }
+
+    pub async fn delete_goal(&self, id: i32) -> ApiResult<()> {
+        let result = sqlx::query("DELETE FROM goals WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(goal_not_found(id));
+        }
+
+        Ok(())
+    }
+
+    async fn find_goal(&self, id: i32) -> ApiResult<Goal> {
+        let query = format!("SELECT {} FROM goals WHERE id = $1", GOAL_COLUMNS);
+        let row = sqlx::query(&query)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| goal_not_found(id))?;
+
+        Ok(row_to_goal(&row))
+    }
+
+    /// Only one goal may count the same metric over the same period
+    async fn ensure_period_available(&self, request: &CreateGoalRequest) -> ApiResult<()> {
+        let taken = sqlx::query(
+            "SELECT 1 FROM goals WHERE metric = $1 AND year = $2 AND month IS NOT DISTINCT FROM $3",
+        )
+        .bind(request.metric)
+        .bind(request.year)
+        .bind(request.month)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match taken {
+            Some(_) => Err(period_taken(request)),
+            None => Ok(()),
+        }
+    }
+
+    /// Count books (or their pages) finished during the goal's period. Each
+    /// finished read-through counts, so a book re-read in the period counts again.
+    async fn with_progress(&self, goal: Goal) -> ApiResult<GoalProgress> {
+        let (start, end) = period_bounds(&goal)?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT COUNT(*) AS books, COALESCE(SUM(books.page_count), 0)::BIGINT AS pages
+            FROM read_throughs
+            JOIN books ON books.id = read_throughs.book_id
+            WHERE read_throughs.finished_on BETWEEN $1 AND $2
+            "#,
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_one(&self.pool)
+        .await?;
+        let completed = match goal.metric {
+            GoalMetric::Books => row.get("books"),
+            GoalMetric::Pages => row.get("pages"),
+        };
+
+        Ok(project(
+            goal,
+            (start, end),
+            completed,
+            Utc::now().date_naive(),
+        ))
+    }
+}
+
+/// First and last day of the goal's year or month
+fn period_bounds(goal: &Goal) -> ApiResult<(NaiveDate, NaiveDate)> {
+    let invalid = || ApiError::InternalError(format!("Goal {} has an invalid period", goal.id));
+
+    let start = NaiveDate::from_ymd_opt(goal.year, goal.month.unwrap_or(1) as u32, 1)
+        .ok_or_else(invalid)?;
+    let length = match goal.period {
+        GoalPeriod::Yearly => Months::new(12),
+        GoalPeriod::Monthly => Months::new(1),
+    };
+    let end = start
+        .checked_add_months(length)
+        .and_then(|next| next.pred_opt())
+        .ok_or_else(invalid)?;
+
+    Ok((start, end))
+}
+
+/// Compare `completed` against an even pace through the period as of `today`
+fn project(
+    goal: Goal,
+    (start, end): (NaiveDate, NaiveDate),
+    completed: i64,
+    today: NaiveDate,
+) -> GoalProgress {
+    let target = i64::from(goal.target);
+    let total_days = (end - start).num_days() + 1;
+    let elapsed_days = ((today.min(end) - start).num_days() + 1).clamp(0, total_days);
+
+    let expected = target * elapsed_days / total_days;
+    let projected = (elapsed_days > 0).then(|| completed * total_days / elapsed_days);
+
+    GoalProgress {
+        goal,
+        period_start: start,
+        period_end: end,
+        completed,
+        remaining: (target - completed).max(0),
+        percent: completed as f64 / target as f64 * 100.0,
+        expected,
+        on_track: completed >= expected,
+        behind_by: (expected - completed).max(0),
+        projected,
+    }
+}
+
+fn period_taken(request: &CreateGoalRequest) -> ApiError {
+    let period = match request.month {
+        Some(month) => format!("{}-{:02}", request.year, month),
+        None => request.year.to_string(),
+    };
+    ApiError::BadRequest(format!(
+        "A {} goal for {} already exists",
+        metric_name(request.metric),
+        period
+    ))
+}
+
+fn metric_name(metric: GoalMetric) -> &'static str {
+    match metric {
+        GoalMetric::Books => "books",
+        GoalMetric::Pages => "pages",
+    }
+}
+
+fn goal_not_found(id: i32) -> ApiError {
+    ApiError::NotFound(format!("Goal with id {} not found", id))
+}
+
+fn row_to_goal(row: &sqlx::postgres::PgRow) -> Goal {
+    Goal {
+        id: row.get("id"),
+        period: row.get("period"),
+        metric: row.get("metric"),
+        year: row.get("year"),
+        month: row.get("month"),
+        target: row.get("target"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn goal(period: GoalPeriod, year: i32, month: Option<i32>, target: i32) -> Goal {
+        Goal {
+            id: 1,
+            period,
+            metric: GoalMetric::Books,
+            year,
+            month,
+            target,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn period_bounds_cover_whole_years_and_months() {
+        let yearly = goal(GoalPeriod::Yearly, 2025, None, 10);
+        assert_eq!(
+            period_bounds(&yearly).unwrap(),
+            (date(2025, 1, 1), date(2025, 12, 31))
+        );
+
+        let leap_february = goal(GoalPeriod::Monthly, 2024, Some(2), 2);
+        assert_eq!(
+            period_bounds(&leap_february).unwrap(),
+            (date(2024, 2, 1), date(2024, 2, 29))
+        );
+
+        let february = goal(GoalPeriod::Monthly, 2025, Some(2), 2);
+        assert_eq!(period_bounds(&february).unwrap().1, date(2025, 2, 28));
+
+        let december = goal(GoalPeriod::Monthly, 2025, Some(12), 2);
+        assert_eq!(
+            period_bounds(&december).unwrap(),
+            (date(2025, 12, 1), date(2025, 12, 31))
+        );
+
+        assert!(period_bounds(&goal(GoalPeriod::Monthly, 2025, Some(13), 2)).is_err());
+    }
+
+    #[test]
+    fn project_paces_a_leap_year_by_its_366_days() {
+        let leap_year = goal(GoalPeriod::Yearly, 2024, None, 366);
+        let bounds = period_bounds(&leap_year).unwrap();
+
+        // Day 60 of 2024 is February 29
+        let progress = project(leap_year, bounds, 50, date(2024, 2, 29));
+        assert_eq!(progress.expected, 60);
+        assert!(!progress.on_track);
+        assert_eq!(progress.behind_by, 10);
+        assert_eq!(progress.projected, Some(50 * 366 / 60));
+    }
+
+    #[test]
+    fn project_before_the_period_expects_nothing() {
+        let future = goal(GoalPeriod::Monthly, 2030, Some(1), 4);
+        let bounds = period_bounds(&future).unwrap();
+
+        let progress = project(future, bounds, 0, date(2029, 12, 31));
+        assert_eq!(progress.expected, 0);
+        assert!(progress.on_track);
+        assert_eq!(progress.remaining, 4);
+        assert_eq!(progress.projected, None);
+    }
+
+    #[test]
+    fn project_after_the_period_expects_the_full_target() {
+        let past = goal(GoalPeriod::Yearly, 2020, None, 12);
+        let bounds = period_bounds(&past).unwrap();
+
+        let progress = project(past, bounds, 15, date(2025, 6, 1));
+        assert_eq!(progress.expected, 12);
+        assert!(progress.on_track);
+        assert_eq!(progress.remaining, 0);
+        assert_eq!(progress.percent, 125.0);
+        assert_eq!(progress.projected, Some(15));
+    }
+
+    #[test]
+    fn project_on_the_last_day_of_a_month_counts_every_day() {
+        let month = goal(GoalPeriod::Monthly, 2025, Some(4), 3);
+        let bounds = period_bounds(&month).unwrap();
+
+        let last_day = project(month.clone(), bounds, 2, date(2025, 4, 30));
+        assert_eq!(last_day.expected, 3);
+        assert_eq!(last_day.behind_by, 1);
+        assert_eq!(last_day.projected, Some(2));
+
+        let first_day = project(month, bounds, 0, date(2025, 4, 1));
+        assert_eq!(first_day.expected, 0);
+        assert_eq!(first_day.projected, Some(0));
+    }
+}
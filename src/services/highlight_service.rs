@@ -0,0 +1,212 @@
+use sqlx::{PgPool, Row};
+use validator::Validate;
+
+use super::ensure_book_exists;
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{
+    CreateHighlightRequest, Highlight, HighlightColor, HighlightSort, UpdateHighlightRequest,
+};
+
+#[derive(Clone)]
+pub struct HighlightService {
+    pool: PgPool,
+}
+
+impl HighlightService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create_highlight(
+        &self,
+        book_id: i32,
+        request: CreateHighlightRequest,
+    ) -> ApiResult<Highlight> {
+        // Validate the request using the validator crate
+        request.validate()?;
+        validate_location_range(request.location_start, request.location_end)?;
+
+        // First check if the parent book exists
+        ensure_book_exists(&self.pool, book_id).await?;
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO highlights (book_id, text, page_number, chapter, percent, location_start, location_end, color)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, book_id, text, page_number, chapter, percent, location_start, location_end, color::text, created_at, updated_at
+            "#,
+        )
+        .bind(book_id)
+        .bind(request.text.trim())
+        .bind(request.page_number)
+        .bind(request.chapter)
+        .bind(request.percent)
+        .bind(request.location_start)
+        .bind(request.location_end)
+        .bind(request.color.unwrap_or_default())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(self.row_to_highlight(&row))
+    }
+
+    pub async fn get_highlight_by_id(&self, id: i32) -> ApiResult<Highlight> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, book_id, text, page_number, chapter, percent, location_start, location_end, color::text, created_at, updated_at
+            FROM highlights
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(self.row_to_highlight(&row))
+    }
+
+    pub async fn get_highlights_for_book(
+        &self,
+        book_id: i32,
+        sort: HighlightSort,
+    ) -> ApiResult<Vec<Highlight>> {
+        ensure_book_exists(&self.pool, book_id).await?;
+
+        // Percent and page anchors share one key as a percentage of the book;
+        // e-reader locations cannot be converted without the book's length in
+        // locations, so they only order highlights that have no such key
+        let order_by = match sort {
+            HighlightSort::Position => {
+                "COALESCE(percent, page_number * 100.0 / NULLIF(books.page_count, 0)) NULLS LAST, \
+                 location_start NULLS LAST, page_number NULLS LAST, created_at, highlights.id"
+            }
+            HighlightSort::Created => "created_at DESC, highlights.id DESC",
+        };
+
+        let query = format!(
+            "SELECT highlights.id, book_id, text, page_number, chapter, percent, location_start, location_end, color::text, created_at, updated_at \
+             FROM highlights JOIN books ON books.id = highlights.book_id \
+             WHERE book_id = $1 ORDER BY {}",
+            order_by
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(book_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let highlights: Vec<Highlight> =
+            rows.iter().map(|row| self.row_to_highlight(row)).collect();
+        Ok(highlights)
+    }
+
+    /// Fetch every highlight in the library, for exports
+    pub async fn list_all_highlights(&self) -> ApiResult<Vec<Highlight>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, book_id, text, page_number, chapter, percent, location_start, location_end, color::text, created_at, updated_at
+            FROM highlights
+            ORDER BY book_id, id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let highlights: Vec<Highlight> =
+            rows.iter().map(|row| self.row_to_highlight(row)).collect();
+        Ok(highlights)
+    }
+
+    pub async fn update_highlight(
+        &self,
+        id: i32,
+        request: UpdateHighlightRequest,
+    ) -> ApiResult<Highlight> {
+        // Validate the request using the validator crate
+        request.validate()?;
+
+        // First check if highlight exists
+        let existing = self.get_highlight_by_id(id).await?;
+        validate_location_range(
+            request.location_start.or(existing.location_start),
+            request.location_end.or(existing.location_end),
+        )?;
+
+        let row = sqlx::query(
+            r#"
+            UPDATE highlights
+            SET
+                text = COALESCE($2, text),
+                page_number = COALESCE($3, page_number),
+                chapter = COALESCE($4, chapter),
+                percent = COALESCE($5, percent),
+                location_start = COALESCE($6, location_start),
+                location_end = COALESCE($7, location_end),
+                color = COALESCE($8, color),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, book_id, text, page_number, chapter, percent, location_start, location_end, color::text, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(request.text.as_deref().map(str::trim))
+        .bind(request.page_number)
+        .bind(request.chapter)
+        .bind(request.percent)
+        .bind(request.location_start)
+        .bind(request.location_end)
+        .bind(request.color)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(self.row_to_highlight(&row))
+    }
+
+    pub async fn delete_highlight(&self, id: i32) -> ApiResult<()> {
+        let result = sqlx::query("DELETE FROM highlights WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound(format!(
+                "Highlight with id {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn row_to_highlight(&self, row: &sqlx::postgres::PgRow) -> Highlight {
+        Highlight {
+            id: row.get("id"),
+            book_id: row.get("book_id"),
+            text: row.get("text"),
+            page_number: row.get("page_number"),
+            chapter: row.get("chapter"),
+            percent: row.get("percent"),
+            location_start: row.get("location_start"),
+            location_end: row.get("location_end"),
+            color: match row.get::<Option<String>, _>("color").as_deref() {
+                Some("blue") => HighlightColor::Blue,
+                Some("pink") => HighlightColor::Pink,
+                Some("orange") => HighlightColor::Orange,
+                _ => HighlightColor::Yellow,
+            },
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+}
+
+fn validate_location_range(start: Option<i32>, end: Option<i32>) -> ApiResult<()> {
+    if let (Some(start), Some(end)) = (start, end) {
+        if end < start {
+            return Err(ApiError::ValidationError(
+                "location_end: must not be before location_start".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,891 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, Utc};
+use serde_json::{json, Value};
+use sqlx::{PgPool, Postgres, Row, Transaction};
+
+use super::book_service::ReadingState;
+use super::read_through_service::sync_read_throughs;
+use super::tag_normalizer::TagNormalizer;
+use crate::errors::{ApiError, ApiResult};
+use crate::importers::backup::parse_backup;
+use crate::importers::goodreads::parse_goodreads_csv;
+use crate::importers::kindle::{parse_clippings, titles_match, Clipping, ClippingKind};
+use crate::importers::storygraph::parse_storygraph_csv;
+use crate::importers::{LibraryEntry, LibraryRow};
+use crate::models::{
+    BackupHighlight, BackupNote, Book, BookStatus, ConflictPolicy, FieldChange, ImportAction,
+    ImportRowResult, KindleImportReport, LibraryImportReport, ReadThrough, RestoreReport,
+};
+
+/// Author used when a source does not provide one
+const UNKNOWN_AUTHOR: &str = "Unknown";
+
+/// Normalized (title, author) pair used to match books across imports
+type BookKey = (String, String);
+
+#[derive(Clone)]
+pub struct ImportService {
+    pool: PgPool,
+    tags: TagNormalizer,
+}
+
+impl ImportService {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            tags: TagNormalizer::default(),
+        }
+    }
+
+    /// Normalize imported and restored tags with `tags`
+    pub fn with_tag_normalizer(mut self, tags: TagNormalizer) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Import highlights and notes from a Kindle `My Clippings.txt` file.
+    ///
+    /// Books are matched by normalized title and author, allowing for titles
+    /// Kindle truncated, and created when missing.
+    /// Clippings that were imported before are skipped, so the same file can be
+    /// imported repeatedly as the device accumulates new entries.
+    pub async fn import_kindle_clippings(&self, input: &str) -> ApiResult<KindleImportReport> {
+        let parsed = parse_clippings(input);
+        let mut report = KindleImportReport {
+            skipped_malformed: parsed.malformed,
+            ..Default::default()
+        };
+
+        let mut tx = self.pool.begin().await?;
+        let mut book_ids: HashMap<BookKey, i32> = HashMap::new();
+
+        for clipping in &parsed.clippings {
+            if clipping.kind == ClippingKind::Bookmark {
+                report.skipped_bookmarks += 1;
+                continue;
+            }
+
+            let author = clipping.author.as_deref().unwrap_or(UNKNOWN_AUTHOR);
+            let key = (normalize(&clipping.title), normalize(author));
+
+            let book_id = match book_ids.get(&key) {
+                Some(id) => *id,
+                None => {
+                    let (id, created) =
+                        find_or_create_book(&mut tx, &clipping.title, author).await?;
+                    if created {
+                        report.books_created += 1;
+                    } else {
+                        report.books_matched += 1;
+                    }
+                    book_ids.insert(key.clone(), id);
+                    id
+                }
+            };
+
+            let import_key = kindle_import_key(&key, clipping);
+            let inserted = if clipping.kind == ClippingKind::Highlight {
+                insert_kindle_highlight(&mut tx, book_id, clipping, &import_key).await?
+            } else {
+                insert_kindle_note(&mut tx, book_id, clipping, &import_key).await?
+            };
+
+            match (inserted, clipping.kind) {
+                (false, _) => report.skipped_duplicates += 1,
+                (true, ClippingKind::Highlight) => report.highlights_created += 1,
+                (true, _) => report.notes_created += 1,
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(report)
+    }
+
+    /// Import a Goodreads library export CSV.
+    ///
+    /// With `dry_run` set, every row is applied inside a transaction that is
+    /// rolled back, so the returned per-row diff is exactly what a real import
+    /// would do.
+    pub async fn import_goodreads(
+        &self,
+        input: &str,
+        dry_run: bool,
+    ) -> ApiResult<LibraryImportReport> {
+        let rows = parse_goodreads_csv(input).map_err(ApiError::BadRequest)?;
+        self.import_library_rows(rows, dry_run).await
+    }
+
+    /// Import a StoryGraph library export CSV, with the same dry-run semantics
+    /// as [`ImportService::import_goodreads`].
+    pub async fn import_storygraph(
+        &self,
+        input: &str,
+        dry_run: bool,
+    ) -> ApiResult<LibraryImportReport> {
+        let rows = parse_storygraph_csv(input).map_err(ApiError::BadRequest)?;
+        self.import_library_rows(rows, dry_run).await
+    }
+
+    /// Restore a JSON backup produced by `GET /api/export`.
+    ///
+    /// Books are restored first so notes, highlights and read-throughs can be
+    /// re-pointed at their new ids. Read-throughs are restored as backed up, and
+    /// the book's status and dates follow them as usual. Everything happens in
+    /// one transaction, so a failing backup leaves the library untouched.
+    pub async fn restore_backup(
+        &self,
+        input: &str,
+        policy: ConflictPolicy,
+    ) -> ApiResult<RestoreReport> {
+        let mut backup = parse_backup(input).map_err(ApiError::BadRequest)?;
+        for book in &mut backup.books {
+            book.tags = self.tags.normalize_all(&book.tags);
+        }
+        let mut report = RestoreReport {
+            backup_version: backup.version,
+            ..Default::default()
+        };
+
+        let mut tx = self.pool.begin().await?;
+
+        // Maps book ids in the backup to ids in this library
+        let mut book_ids: HashMap<i32, i32> = HashMap::new();
+        // Books this restore created or matched; each existing book absorbs at
+        // most one backed-up book, and restored books are never matched again
+        let mut claimed: Vec<i32> = Vec::new();
+
+        for book in &backup.books {
+            let existing = match policy {
+                ConflictPolicy::Duplicate => None,
+                _ => {
+                    find_book_by_title_author(&mut tx, &book.title, &book.author, &claimed).await?
+                }
+            };
+
+            match existing {
+                Some(id) if policy == ConflictPolicy::Skip => {
+                    claimed.push(id);
+                    report.books_skipped += 1;
+                }
+                Some(id) => {
+                    overwrite_backup_book(&mut tx, id, book).await?;
+                    book_ids.insert(book.id, id);
+                    claimed.push(id);
+                    report.books_overwritten += 1;
+                }
+                None => {
+                    let id = insert_backup_book(&mut tx, book).await?;
+                    book_ids.insert(book.id, id);
+                    claimed.push(id);
+                    report.books_created += 1;
+                }
+            }
+        }
+
+        for backup_note in &backup.notes {
+            if let Some(&book_id) = book_ids.get(&backup_note.note.book_id) {
+                insert_backup_note(&mut tx, book_id, backup_note).await?;
+                report.notes_restored += 1;
+            }
+        }
+
+        for backup_highlight in &backup.highlights {
+            if let Some(&book_id) = book_ids.get(&backup_highlight.highlight.book_id) {
+                insert_backup_highlight(&mut tx, book_id, backup_highlight).await?;
+                report.highlights_restored += 1;
+            }
+        }
+
+        for read_through in &backup.read_throughs {
+            if let Some(&book_id) = book_ids.get(&read_through.book_id) {
+                insert_backup_read_through(&mut tx, book_id, read_through).await?;
+                report.read_throughs_restored += 1;
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(report)
+    }
+
+    async fn import_library_rows(
+        &self,
+        rows: Vec<LibraryRow>,
+        dry_run: bool,
+    ) -> ApiResult<LibraryImportReport> {
+        let mut report = LibraryImportReport {
+            dry_run,
+            ..Default::default()
+        };
+
+        let mut tx = self.pool.begin().await?;
+
+        for LibraryRow { row, entry } in rows {
+            let result = match entry {
+                Ok(mut entry) => {
+                    entry.tags = self.tags.normalize_all(&entry.tags);
+                    apply_library_entry(&mut tx, row, &entry, dry_run).await?
+                }
+                Err(error) => ImportRowResult {
+                    row,
+                    title: None,
+                    author: None,
+                    book_id: None,
+                    action: ImportAction::Error,
+                    changes: Vec::new(),
+                    error: Some(error),
+                },
+            };
+
+            match result.action {
+                ImportAction::Create => report.created += 1,
+                ImportAction::Update => report.updated += 1,
+                ImportAction::Unchanged => report.unchanged += 1,
+                ImportAction::Error => report.failed += 1,
+            }
+            report.rows.push(result);
+        }
+
+        if dry_run {
+            tx.rollback().await?;
+        } else {
+            tx.commit().await?;
+        }
+
+        Ok(report)
+    }
+}
+
+/// Library fields that an import may change on a book
+#[derive(PartialEq)]
+struct ExistingBook {
+    id: i32,
+    status: Option<BookStatus>,
+    rating: Option<i32>,
+    date_added: Option<NaiveDate>,
+    date_started: Option<NaiveDate>,
+    date_finished: Option<NaiveDate>,
+    isbn: Option<String>,
+    isbn13: Option<String>,
+    tags: Vec<String>,
+}
+
+impl ExistingBook {
+    fn reading_state(&self) -> ReadingState {
+        ReadingState {
+            status: self.status.unwrap_or(BookStatus::Wishlist),
+            date_started: self.date_started,
+            date_finished: self.date_finished,
+        }
+    }
+
+    /// Field names and values, in the order changes are reported
+    fn fields(&self) -> [(&'static str, Value); 8] {
+        [
+            ("status", json!(self.status)),
+            ("rating", json!(self.rating)),
+            ("date_added", json!(self.date_added)),
+            ("date_started", json!(self.date_started)),
+            ("date_finished", json!(self.date_finished)),
+            ("isbn", json!(self.isbn)),
+            ("isbn13", json!(self.isbn13)),
+            ("tags", json!(self.tags)),
+        ]
+    }
+}
+
+/// Columns read into an [`ExistingBook`]
+const LIBRARY_COLUMNS: &str =
+    "id, status, rating, date_added, date_started, date_finished, isbn, isbn13, tags";
+
+/// Create or update the book described by an imported row and record the diff.
+///
+/// The row's status and finish date go through the same transition as a book
+/// update, so missing dates are stamped and contradictory rows are reported as
+/// errors. Changes are read back after writing, so a dry run shows exactly what
+/// would be stored.
+async fn apply_library_entry(
+    tx: &mut Transaction<'_, Postgres>,
+    row: usize,
+    entry: &LibraryEntry,
+    dry_run: bool,
+) -> ApiResult<ImportRowResult> {
+    let mut result = ImportRowResult {
+        row,
+        title: Some(entry.title.clone()),
+        author: Some(entry.author.clone()),
+        book_id: None,
+        action: ImportAction::Unchanged,
+        changes: Vec::new(),
+        error: None,
+    };
+
+    let existing = find_library_match(tx, entry).await?;
+    let reading_changed = entry.status.is_some() || entry.date_finished.is_some();
+    let stored = existing
+        .as_ref()
+        .map_or_else(ReadingState::default, ExistingBook::reading_state);
+    // A new book with only a finish date was read, whatever shelf it is on
+    let status = match existing {
+        Some(_) => entry.status,
+        None => entry
+            .status
+            .or(entry.date_finished.map(|_| BookStatus::Finished)),
+    };
+    let state = if reading_changed || existing.is_none() {
+        match stored.transition(status, None, entry.date_finished) {
+            Ok(state) => state,
+            Err(ApiError::ValidationError(message)) => {
+                result.book_id = existing.map(|book| book.id);
+                result.action = ImportAction::Error;
+                result.error = Some(message);
+                return Ok(result);
+            }
+            Err(e) => return Err(e),
+        }
+    } else {
+        stored
+    };
+
+    let Some(existing) = existing else {
+        let id = insert_library_entry(tx, entry, state).await?;
+        let created = read_library_fields(tx, id).await?;
+        result.book_id = (!dry_run).then_some(id);
+        result.action = ImportAction::Create;
+        result.changes = vec![
+            FieldChange {
+                field: "title".to_string(),
+                from: Value::Null,
+                to: json!(entry.title),
+            },
+            FieldChange {
+                field: "author".to_string(),
+                from: Value::Null,
+                to: json!(entry.author),
+            },
+        ];
+        result.changes.extend(library_changes(None, &created));
+        return Ok(result);
+    };
+
+    result.book_id = Some(existing.id);
+
+    // Imported tags are merged into the existing ones rather than replacing them
+    let mut tags = existing.tags.clone();
+    for tag in &entry.tags {
+        if !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
+    }
+
+    let updated = ExistingBook {
+        id: existing.id,
+        status: Some(state.status),
+        rating: entry.rating.or(existing.rating),
+        date_added: entry.date_added.or(existing.date_added),
+        date_started: state.date_started,
+        date_finished: state.date_finished,
+        isbn: entry.isbn.clone().or(existing.isbn.clone()),
+        isbn13: entry.isbn13.clone().or(existing.isbn13.clone()),
+        tags,
+    };
+    if updated == existing {
+        return Ok(result);
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE books
+        SET status = $2, rating = $3, date_added = $4, date_started = $5, date_finished = $6,
+            isbn = $7, isbn13 = $8, tags = $9,
+            abandon_reason = CASE WHEN $2 = 'abandoned' THEN abandon_reason END,
+            abandon_page = CASE WHEN $2 = 'abandoned' THEN abandon_page END
+        WHERE id = $1
+        "#,
+    )
+    .bind(existing.id)
+    .bind(updated.status)
+    .bind(updated.rating)
+    .bind(updated.date_added)
+    .bind(updated.date_started)
+    .bind(updated.date_finished)
+    .bind(&updated.isbn)
+    .bind(&updated.isbn13)
+    .bind(&updated.tags)
+    .execute(&mut **tx)
+    .await?;
+
+    if reading_changed {
+        sync_read_throughs(
+            tx,
+            existing.id,
+            Some(state.status),
+            state.date_finished,
+            state.date_started,
+        )
+        .await?;
+    }
+
+    let stored = read_library_fields(tx, existing.id).await?;
+    result.changes = library_changes(Some(&existing), &stored);
+    if !result.changes.is_empty() {
+        result.action = ImportAction::Update;
+    }
+    Ok(result)
+}
+
+/// Fields that differ between `before` and `after`; without `before`, every
+/// field `after` has a value for
+fn library_changes(before: Option<&ExistingBook>, after: &ExistingBook) -> Vec<FieldChange> {
+    let before = before.map(ExistingBook::fields);
+    after
+        .fields()
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, (field, to))| {
+            let from = before
+                .as_ref()
+                .map_or(Value::Null, |before| before[index].1.clone());
+            (from != to && !to.is_null()).then(|| FieldChange {
+                field: field.to_string(),
+                from,
+                to,
+            })
+        })
+        .collect()
+}
+
+/// Match an imported row by ISBN-13, then ISBN-10, then normalized title and author
+async fn find_library_match(
+    tx: &mut Transaction<'_, Postgres>,
+    entry: &LibraryEntry,
+) -> ApiResult<Option<ExistingBook>> {
+    let query = format!(
+        r#"
+        SELECT {}
+        FROM books
+        WHERE ($1::TEXT IS NOT NULL AND isbn13 = $1)
+           OR ($2::TEXT IS NOT NULL AND isbn = $2)
+           OR (regexp_replace(lower(btrim(title)), '\s+', ' ', 'g') = $3
+               AND regexp_replace(lower(btrim(author)), '\s+', ' ', 'g') = $4)
+        ORDER BY (isbn13 = $1) IS TRUE DESC, (isbn = $2) IS TRUE DESC, id
+        LIMIT 1
+        "#,
+        LIBRARY_COLUMNS
+    );
+    let row = sqlx::query(&query)
+        .bind(entry.isbn13.as_deref())
+        .bind(entry.isbn.as_deref())
+        .bind(normalize(&entry.title))
+        .bind(normalize(&entry.author))
+        .fetch_optional(&mut **tx)
+        .await?;
+
+    Ok(row.as_ref().map(row_to_library_fields))
+}
+
+/// The library fields of book `id` as currently stored, including those the
+/// read-through triggers derived
+async fn read_library_fields(
+    tx: &mut Transaction<'_, Postgres>,
+    id: i32,
+) -> ApiResult<ExistingBook> {
+    let query = format!("SELECT {} FROM books WHERE id = $1", LIBRARY_COLUMNS);
+    let row = sqlx::query(&query).bind(id).fetch_one(&mut **tx).await?;
+
+    Ok(row_to_library_fields(&row))
+}
+
+fn row_to_library_fields(row: &sqlx::postgres::PgRow) -> ExistingBook {
+    ExistingBook {
+        id: row.get("id"),
+        status: row.get("status"),
+        rating: row.get("rating"),
+        date_added: row.get("date_added"),
+        date_started: row.get("date_started"),
+        date_finished: row.get("date_finished"),
+        isbn: row.get("isbn"),
+        isbn13: row.get("isbn13"),
+        tags: row
+            .get::<Option<Vec<String>>, _>("tags")
+            .unwrap_or_default(),
+    }
+}
+
+async fn insert_library_entry(
+    tx: &mut Transaction<'_, Postgres>,
+    entry: &LibraryEntry,
+    state: ReadingState,
+) -> ApiResult<i32> {
+    let row = sqlx::query(
+        r#"
+        INSERT INTO books (title, author, tags, status, date_added, date_started, date_finished, rating, notes_count, isbn, isbn13)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, 0, $9, $10)
+        RETURNING id
+        "#,
+    )
+    .bind(truncate(&entry.title))
+    .bind(truncate(&entry.author))
+    .bind(&entry.tags)
+    .bind(state.status)
+    .bind(entry.date_added.unwrap_or_else(|| Utc::now().date_naive()))
+    .bind(state.date_started)
+    .bind(state.date_finished)
+    .bind(entry.rating)
+    .bind(entry.isbn.as_deref())
+    .bind(entry.isbn13.as_deref())
+    .fetch_one(&mut **tx)
+    .await?;
+    let id = row.get("id");
+
+    sync_read_throughs(
+        tx,
+        id,
+        Some(state.status),
+        state.date_finished,
+        state.date_started,
+    )
+    .await?;
+
+    Ok(id)
+}
+
+/// Lowercase and collapse whitespace so titles and authors compare loosely
+fn normalize(value: &str) -> String {
+    value
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Truncate to the 255 character limit enforced on book fields
+fn truncate(value: &str) -> String {
+    value.trim().chars().take(255).collect()
+}
+
+/// Find the oldest book with the same normalized title and author, ignoring
+/// the ids in `exclude`
+async fn find_book_by_title_author(
+    tx: &mut Transaction<'_, Postgres>,
+    title: &str,
+    author: &str,
+    exclude: &[i32],
+) -> ApiResult<Option<i32>> {
+    let existing = sqlx::query(
+        r#"
+        SELECT id
+        FROM books
+        WHERE regexp_replace(lower(btrim(title)), '\s+', ' ', 'g') = $1
+          AND regexp_replace(lower(btrim(author)), '\s+', ' ', 'g') = $2
+          AND id <> ALL($3)
+        ORDER BY id
+        LIMIT 1
+        "#,
+    )
+    .bind(normalize(title))
+    .bind(normalize(author))
+    .bind(exclude)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(existing.map(|row| row.get("id")))
+}
+
+/// Find the oldest book by the same author whose title matches a Kindle
+/// title that may have been truncated
+async fn find_book_by_truncated_title(
+    tx: &mut Transaction<'_, Postgres>,
+    title: &str,
+    author: &str,
+) -> ApiResult<Option<i32>> {
+    let title = normalize(title);
+    let prefix = title
+        .trim_end_matches("...")
+        .trim_end_matches('\u{2026}')
+        .trim_end();
+
+    let candidates = sqlx::query(
+        r#"
+        SELECT id, title
+        FROM books
+        WHERE regexp_replace(lower(btrim(author)), '\s+', ' ', 'g') = $2
+          AND (starts_with(regexp_replace(lower(btrim(title)), '\s+', ' ', 'g'), $1)
+               OR starts_with($1, regexp_replace(lower(btrim(title)), '\s+', ' ', 'g')))
+        ORDER BY id
+        "#,
+    )
+    .bind(prefix)
+    .bind(normalize(author))
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(candidates
+        .iter()
+        .find(|row| titles_match(&title, &normalize(row.get("title"))))
+        .map(|row| row.get("id")))
+}
+
+/// Find a book by normalized title and author, tolerating titles Kindle
+/// truncated, and create it if none exists.
+/// Returns the book id and whether it was created.
+async fn find_or_create_book(
+    tx: &mut Transaction<'_, Postgres>,
+    title: &str,
+    author: &str,
+) -> ApiResult<(i32, bool)> {
+    if let Some(id) = find_book_by_title_author(tx, title, author, &[]).await? {
+        return Ok((id, false));
+    }
+    if let Some(id) = find_book_by_truncated_title(tx, title, author).await? {
+        return Ok((id, false));
+    }
+
+    let row = sqlx::query(
+        r#"
+        INSERT INTO books (title, author, tags, notes_count)
+        VALUES ($1, $2, '{}', 0)
+        RETURNING id
+        "#,
+    )
+    .bind(truncate(title))
+    .bind(truncate(author))
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok((row.get("id"), true))
+}
+
+/// Stable identity of a clipping, hashed in the database with `md5()`
+fn kindle_import_key(book_key: &BookKey, clipping: &Clipping) -> String {
+    format!(
+        "kindle|{}|{}|{:?}|{:?}|{:?}|{:?}|{}",
+        book_key.0,
+        book_key.1,
+        clipping.kind,
+        clipping.page,
+        clipping.location_start,
+        clipping.location_end,
+        clipping.content
+    )
+}
+
+async fn insert_kindle_highlight(
+    tx: &mut Transaction<'_, Postgres>,
+    book_id: i32,
+    clipping: &Clipping,
+    import_key: &str,
+) -> ApiResult<bool> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO highlights (book_id, text, page_number, location_start, location_end, import_key, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, md5($6), COALESCE($7, NOW()), COALESCE($7, NOW()))
+        ON CONFLICT (import_key) DO NOTHING
+        "#,
+    )
+    .bind(book_id)
+    .bind(&clipping.content)
+    .bind(clipping.page)
+    .bind(clipping.location_start)
+    .bind(clipping.location_end)
+    .bind(import_key)
+    .bind(clipping.added_at)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+async fn insert_kindle_note(
+    tx: &mut Transaction<'_, Postgres>,
+    book_id: i32,
+    clipping: &Clipping,
+    import_key: &str,
+) -> ApiResult<bool> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO notes (book_id, content, page_number, import_key, created_at, updated_at)
+        VALUES ($1, $2, $3, md5($4), COALESCE($5, NOW()), COALESCE($5, NOW()))
+        ON CONFLICT (import_key) DO NOTHING
+        "#,
+    )
+    .bind(book_id)
+    .bind(&clipping.content)
+    .bind(clipping.page)
+    .bind(import_key)
+    .bind(clipping.added_at)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+async fn insert_backup_book(tx: &mut Transaction<'_, Postgres>, book: &Book) -> ApiResult<i32> {
+    let row = sqlx::query(
+        r#"
+        INSERT INTO books (title, author, cover_url, tags, status, date_added, date_started, date_finished, rating, description, notes_count, isbn, isbn13, page_count, audio_minutes, abandon_reason, abandon_page)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, 0, $11, $12, $13, $14, $15, $16)
+        RETURNING id
+        "#,
+    )
+    .bind(&book.title)
+    .bind(&book.author)
+    .bind(book.cover_url.as_deref())
+    .bind(&book.tags)
+    .bind(book.status)
+    .bind(book.date_added)
+    .bind(book.date_started)
+    .bind(book.date_finished)
+    .bind(book.rating)
+    .bind(book.description.as_deref())
+    .bind(book.isbn.as_deref())
+    .bind(book.isbn13.as_deref())
+    .bind(book.page_count)
+    .bind(book.audio_minutes)
+    .bind(book.abandon_reason.as_deref())
+    .bind(book.abandon_page)
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(row.get("id"))
+}
+
+/// Replace an existing book's fields, read-throughs, notes and highlights with
+/// the backed-up ones
+async fn overwrite_backup_book(
+    tx: &mut Transaction<'_, Postgres>,
+    id: i32,
+    book: &Book,
+) -> ApiResult<()> {
+    // Dropping the read-throughs first resets the derived reading state, which
+    // the backed-up fields and read-throughs then replace
+    sqlx::query("DELETE FROM read_throughs WHERE book_id = $1")
+        .bind(id)
+        .execute(&mut **tx)
+        .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE books
+        SET title = $2, author = $3, cover_url = $4, tags = $5, status = $6, date_added = $7,
+            date_finished = $8, rating = $9, description = $10, isbn = $11, isbn13 = $12,
+            page_count = $13, audio_minutes = $14, date_started = $15, abandon_reason = $16,
+            abandon_page = $17
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(&book.title)
+    .bind(&book.author)
+    .bind(book.cover_url.as_deref())
+    .bind(&book.tags)
+    .bind(book.status)
+    .bind(book.date_added)
+    .bind(book.date_finished)
+    .bind(book.rating)
+    .bind(book.description.as_deref())
+    .bind(book.isbn.as_deref())
+    .bind(book.isbn13.as_deref())
+    .bind(book.page_count)
+    .bind(book.audio_minutes)
+    .bind(book.date_started)
+    .bind(book.abandon_reason.as_deref())
+    .bind(book.abandon_page)
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query("DELETE FROM notes WHERE book_id = $1")
+        .bind(id)
+        .execute(&mut **tx)
+        .await?;
+    sqlx::query("DELETE FROM highlights WHERE book_id = $1")
+        .bind(id)
+        .execute(&mut **tx)
+        .await?;
+
+    Ok(())
+}
+
+/// Restore a note, keeping its import key unless a clipping already owns it
+async fn insert_backup_note(
+    tx: &mut Transaction<'_, Postgres>,
+    book_id: i32,
+    backup_note: &BackupNote,
+) -> ApiResult<()> {
+    let note = &backup_note.note;
+    sqlx::query(
+        r#"
+        INSERT INTO notes (book_id, content, page_number, chapter, created_at, updated_at, import_key)
+        SELECT $1, $2, $3, $4, $5, $6,
+               CASE WHEN EXISTS (SELECT 1 FROM notes WHERE import_key = $7) THEN NULL ELSE $7 END
+        "#,
+    )
+    .bind(book_id)
+    .bind(&note.content)
+    .bind(note.page_number)
+    .bind(note.chapter.as_deref())
+    .bind(note.created_at)
+    .bind(note.updated_at)
+    .bind(backup_note.import_key.as_deref())
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Restore a highlight, keeping its import key unless a clipping already owns it
+async fn insert_backup_highlight(
+    tx: &mut Transaction<'_, Postgres>,
+    book_id: i32,
+    backup_highlight: &BackupHighlight,
+) -> ApiResult<()> {
+    let highlight = &backup_highlight.highlight;
+    sqlx::query(
+        r#"
+        INSERT INTO highlights (book_id, text, page_number, chapter, percent, location_start, location_end, color, created_at, updated_at, import_key)
+        SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10,
+               CASE WHEN EXISTS (SELECT 1 FROM highlights WHERE import_key = $11) THEN NULL ELSE $11 END
+        "#,
+    )
+    .bind(book_id)
+    .bind(&highlight.text)
+    .bind(highlight.page_number)
+    .bind(highlight.chapter.as_deref())
+    .bind(highlight.percent)
+    .bind(highlight.location_start)
+    .bind(highlight.location_end)
+    .bind(highlight.color)
+    .bind(highlight.created_at)
+    .bind(highlight.updated_at)
+    .bind(backup_highlight.import_key.as_deref())
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_backup_read_through(
+    tx: &mut Transaction<'_, Postgres>,
+    book_id: i32,
+    read_through: &ReadThrough,
+) -> ApiResult<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO read_throughs (book_id, started_on, finished_on, rating, format, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(book_id)
+    .bind(read_through.started_on)
+    .bind(read_through.finished_on)
+    .bind(read_through.rating)
+    .bind(read_through.format)
+    .bind(read_through.created_at)
+    .bind(read_through.updated_at)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
@@ -1,28 +1,102 @@
+pub(crate) mod book_query;
 pub mod book_service;
+pub mod collection_service;
+pub mod export_service;
+pub mod goal_service;
+pub mod highlight_service;
+pub mod import_service;
+pub mod note_service;
+pub mod progress_service;
+pub mod read_through_service;
+pub mod reading_session_service;
+pub mod search_service;
+pub mod stats_service;
+pub mod tag_normalizer;
+pub mod tag_service;
 
 use sqlx::PgPool;
 
+use crate::errors::{ApiError, ApiResult};
+
 pub use book_service::BookService;
+pub use collection_service::CollectionService;
+pub use export_service::ExportService;
+pub use goal_service::GoalService;
+pub use highlight_service::HighlightService;
+pub use import_service::ImportService;
+pub use note_service::NoteService;
+pub use progress_service::ProgressService;
+pub use read_through_service::ReadThroughService;
+pub use reading_session_service::ReadingSessionService;
+pub use search_service::SearchService;
+pub use stats_service::StatsService;
+pub use tag_normalizer::{TagAliases, TagNormalizer};
+pub use tag_service::TagService;
 
 /// Application state that holds all services
 #[derive(Clone)]
 pub struct AppState {
     pub book_service: BookService,
+    pub note_service: NoteService,
+    pub highlight_service: HighlightService,
+    pub import_service: ImportService,
+    pub export_service: ExportService,
+    pub search_service: SearchService,
+    pub tag_service: TagService,
+    pub collection_service: CollectionService,
+    pub progress_service: ProgressService,
+    pub reading_session_service: ReadingSessionService,
+    pub read_through_service: ReadThroughService,
+    pub stats_service: StatsService,
+    pub goal_service: GoalService,
     // Future services can be added here:
-    // pub note_service: NoteService,
     // pub user_service: UserService,
     // pub auth_service: AuthService,
 }
 
 impl AppState {
     /// Create a new AppState with all services initialized
-    pub fn new(pool: PgPool) -> Self {
+    pub fn new(pool: PgPool, tags: TagNormalizer) -> Self {
         Self {
-            book_service: BookService::new(pool.clone()),
+            book_service: BookService::new(pool.clone()).with_tag_normalizer(tags.clone()),
+            note_service: NoteService::new(pool.clone()),
+            highlight_service: HighlightService::new(pool.clone()),
+            import_service: ImportService::new(pool.clone()).with_tag_normalizer(tags.clone()),
+            export_service: ExportService::new(pool.clone()),
+            search_service: SearchService::new(pool.clone()),
+            tag_service: TagService::new(pool.clone()).with_tag_normalizer(tags),
+            collection_service: CollectionService::new(pool.clone()),
+            progress_service: ProgressService::new(pool.clone()),
+            reading_session_service: ReadingSessionService::new(pool.clone()),
+            read_through_service: ReadThroughService::new(pool.clone()),
+            stats_service: StatsService::new(pool.clone()),
+            goal_service: GoalService::new(pool.clone()),
             // Future services initialization:
-            // note_service: NoteService::new(pool.clone()),
             // user_service: UserService::new(pool.clone()),
             // auth_service: AuthService::new(pool),
         }
     }
 }
+
+/// Return `NotFound` unless a book with the given id exists
+pub(crate) async fn ensure_book_exists(pool: &PgPool, book_id: i32) -> ApiResult<()> {
+    let exists = sqlx::query("SELECT 1 FROM books WHERE id = $1")
+        .bind(book_id)
+        .fetch_optional(pool)
+        .await?;
+
+    if exists.is_none() {
+        return Err(ApiError::NotFound(format!(
+            "Book with id {} not found",
+            book_id
+        )));
+    }
+
+    Ok(())
+}
+
+/// True when `error` is a unique index violation, e.g. from a concurrent insert
+/// that got past an earlier availability check
+pub(crate) fn is_unique_violation(error: &sqlx::Error) -> bool {
+    matches!(error, sqlx::Error::Database(db_error) if db_error.is_unique_violation())
+}
@@ -0,0 +1,152 @@
+use sqlx::{PgPool, Row};
+use validator::Validate;
+
+use super::ensure_book_exists;
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{CreateNoteRequest, Note, UpdateNoteRequest};
+
+#[derive(Clone)]
+pub struct NoteService {
+    pool: PgPool,
+}
+
+impl NoteService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create_note(&self, book_id: i32, request: CreateNoteRequest) -> ApiResult<Note> {
+        // Validate the request using the validator crate
+        request.validate()?;
+
+        // First check if the parent book exists
+        ensure_book_exists(&self.pool, book_id).await?;
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO notes (book_id, content, page_number, chapter)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, book_id, content, page_number, chapter, created_at, updated_at
+            "#,
+        )
+        .bind(book_id)
+        .bind(request.content.trim())
+        .bind(request.page_number)
+        .bind(request.chapter)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(self.row_to_note(&row))
+    }
+
+    pub async fn get_note_by_id(&self, id: i32) -> ApiResult<Note> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, book_id, content, page_number, chapter, created_at, updated_at
+            FROM notes
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(self.row_to_note(&row))
+    }
+
+    pub async fn get_notes_for_book(&self, book_id: i32) -> ApiResult<Vec<Note>> {
+        ensure_book_exists(&self.pool, book_id).await?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT id, book_id, content, page_number, chapter, created_at, updated_at
+            FROM notes
+            WHERE book_id = $1
+            ORDER BY created_at DESC, id DESC
+            "#,
+        )
+        .bind(book_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let notes: Vec<Note> = rows.iter().map(|row| self.row_to_note(row)).collect();
+        Ok(notes)
+    }
+
+    /// Fetch every note in the library, for exports
+    pub async fn list_all_notes(&self) -> ApiResult<Vec<Note>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, book_id, content, page_number, chapter, created_at, updated_at
+            FROM notes
+            ORDER BY book_id, id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let notes: Vec<Note> = rows.iter().map(|row| self.row_to_note(row)).collect();
+        Ok(notes)
+    }
+
+    pub async fn update_note(&self, id: i32, request: UpdateNoteRequest) -> ApiResult<Note> {
+        // Validate the request using the validator crate
+        request.validate()?;
+
+        // First check if note exists
+        self.get_note_by_id(id).await?;
+
+        // Moving a note requires the target book to exist
+        if let Some(book_id) = request.book_id {
+            ensure_book_exists(&self.pool, book_id).await?;
+        }
+
+        let row = sqlx::query(
+            r#"
+            UPDATE notes
+            SET
+                content = COALESCE($2, content),
+                page_number = COALESCE($3, page_number),
+                chapter = COALESCE($4, chapter),
+                book_id = COALESCE($5, book_id),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, book_id, content, page_number, chapter, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(request.content.as_deref().map(str::trim))
+        .bind(request.page_number)
+        .bind(request.chapter)
+        .bind(request.book_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(self.row_to_note(&row))
+    }
+
+    pub async fn delete_note(&self, id: i32) -> ApiResult<()> {
+        let result = sqlx::query("DELETE FROM notes WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound(format!("Note with id {} not found", id)));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn row_to_note(&self, row: &sqlx::postgres::PgRow) -> Note {
+        Note {
+            id: row.get("id"),
+            book_id: row.get("book_id"),
+            content: row.get("content"),
+            page_number: row.get("page_number"),
+            chapter: row.get("chapter"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use sqlx::{PgPool, Row};
+use validator::Validate;
+
+use super::BookService;
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{
+    Book, BookDetail, BookStatus, ProgressSummary, ReadingProgress, RecordProgressRequest,
+};
+
+#[derive(Clone)]
+pub struct ProgressService {
+    pool: PgPool,
+    book_service: BookService,
+}
+
+impl ProgressService {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            book_service: BookService::new(pool.clone()),
+            pool,
+        }
+    }
+
+    /// Record how far into a book the reader is, by page or by percent
+    pub async fn record_progress(
+        &self,
+        book_id: i32,
+        request: RecordProgressRequest,
+    ) -> ApiResult<ReadingProgress> {
+        // Validate the request using the validator crate
+        request.validate()?;
+        if request.current_page.is_none() && request.percent.is_none() {
+            return Err(ApiError::ValidationError(
+                "current_page or percent is required".to_string(),
+            ));
+        }
+
+        let book = self.book_service.get_book_by_id(book_id).await?;
+        if let (Some(page), Some(page_count)) = (request.current_page, book.page_count) {
+            if page > page_count {
+                return Err(ApiError::ValidationError(format!(
+                    "current_page: must not exceed the book's {} pages",
+                    page_count
+                )));
+            }
+        }
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO reading_progress (book_id, current_page, percent, recorded_at)
+            VALUES ($1, $2, $3, COALESCE($4, NOW()))
+            RETURNING id, book_id, current_page, percent, recorded_at
+            "#,
+        )
+        .bind(book_id)
+        .bind(request.current_page)
+        .bind(request.percent)
+        .bind(request.recorded_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row_to_progress(&row, book.page_count))
+    }
+
+    /// Every progress update for a book, oldest first
+    pub async fn get_progress_for_book(&self, book_id: i32) -> ApiResult<Vec<ReadingProgress>> {
+        let book = self.book_service.get_book_by_id(book_id).await?;
+        self.history(&book, None).await
+    }
+
+    /// A book plus, while it is being read, its progress and projected finish.
+    /// Only updates since the current read began count, so an earlier read's
+    /// progress does not skew the pace of a re-read.
+    pub async fn get_book_detail(&self, id: i32) -> ApiResult<BookDetail> {
+        let book = self.book_service.get_book_by_id(id).await?;
+
+        let progress = match book.status {
+            BookStatus::Reading => {
+                let since = self.current_read_start(&book).await?;
+                summarize(&self.history(&book, since).await?)
+            }
+            _ => None,
+        };
+
+        Ok(BookDetail { book, progress })
+    }
+
+    /// Start of the read-through in progress, or when it was logged if it has
+    /// no start date, falling back to the book's `date_started`
+    async fn current_read_start(&self, book: &Book) -> ApiResult<Option<DateTime<Utc>>> {
+        let open = sqlx::query(
+            "SELECT started_on, created_at FROM read_throughs WHERE book_id = $1 AND finished_on IS NULL",
+        )
+        .bind(book.id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(match open {
+            Some(row) => Some(
+                row.get::<Option<NaiveDate>, _>("started_on")
+                    .map(start_of_day)
+                    .unwrap_or_else(|| row.get("created_at")),
+            ),
+            None => book.date_started.map(start_of_day),
+        })
+    }
+
+    /// Progress updates for `book`, oldest first, optionally only those
+    /// recorded from `since` on
+    async fn history(
+        &self,
+        book: &Book,
+        since: Option<DateTime<Utc>>,
+    ) -> ApiResult<Vec<ReadingProgress>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, book_id, current_page, percent, recorded_at
+            FROM reading_progress
+            WHERE book_id = $1
+              AND ($2::TIMESTAMPTZ IS NULL OR recorded_at >= $2)
+            ORDER BY recorded_at, id
+            "#,
+        )
+        .bind(book.id)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| row_to_progress(row, book.page_count))
+            .collect())
+    }
+}
+
+/// Time of a progress update and the percent read at that point
+type Measurement = (DateTime<Utc>, f64);
+
+/// Latest position and a linear projection of the finish date.
+///
+/// The pace is the average percent per day between the first and the latest
+/// update; without two updates that moved forward there is no estimate.
+fn summarize(history: &[ReadingProgress]) -> Option<ProgressSummary> {
+    let latest = history.last()?;
+
+    let measured: Vec<Measurement> = history
+        .iter()
+        .filter_map(|progress| Some((progress.recorded_at, progress.percent?)))
+        .collect();
+
+    let percent_per_day = match (measured.first(), measured.last()) {
+        (Some((first_at, first)), Some((last_at, last))) => {
+            let days = (*last_at - *first_at).num_seconds() as f64 / 86_400.0;
+            (days > 0.0 && last > first).then(|| (last - first) / days)
+        }
+        _ => None,
+    };
+
+    let estimated_completion = match (measured.last(), percent_per_day) {
+        (Some((last_at, last)), _) if *last >= 100.0 => Some(last_at.date_naive()),
+        (Some((last_at, last)), Some(rate)) => {
+            let remaining_days = ((100.0 - last) / rate).ceil() as i64;
+            Duration::try_days(remaining_days)
+                .and_then(|remaining| last_at.checked_add_signed(remaining))
+                .map(|finish| finish.date_naive())
+        }
+        _ => None,
+    };
+
+    Some(ProgressSummary {
+        current_page: latest.current_page,
+        percent: latest.percent,
+        updated_at: latest.recorded_at,
+        percent_per_day,
+        estimated_completion,
+    })
+}
+
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(NaiveTime::MIN).and_utc()
+}
+
+fn row_to_progress(row: &sqlx::postgres::PgRow, page_count: Option<i32>) -> ReadingProgress {
+    let current_page: Option<i32> = row.get("current_page");
+    let percent: Option<f64> = row.get("percent");
+
+    ReadingProgress {
+        id: row.get("id"),
+        book_id: row.get("book_id"),
+        current_page,
+        percent: percent.or_else(|| {
+            let (page, count) = (current_page?, page_count?);
+            Some((page as f64 / count as f64 * 100.0).min(100.0))
+        }),
+        recorded_at: row.get("recorded_at"),
+    }
+}
@@ -0,0 +1,365 @@
+use chrono::{NaiveDate, Utc};
+use sqlx::{PgPool, Postgres, Row, Transaction};
+use validator::Validate;
+
+use super::{ensure_book_exists, is_unique_violation};
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{BookStatus, CreateReadThroughRequest, ReadThrough, UpdateReadThroughRequest};
+
+/// Read-throughs are listed oldest first, with the one in progress last
+const READ_THROUGH_ORDER: &str =
+    "finished_on IS NULL, COALESCE(finished_on, started_on) NULLS FIRST, id";
+
+#[derive(Clone)]
+pub struct ReadThroughService {
+    pool: PgPool,
+}
+
+impl ReadThroughService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get_read_throughs_for_book(&self, book_id: i32) -> ApiResult<Vec<ReadThrough>> {
+        ensure_book_exists(&self.pool, book_id).await?;
+
+        let query = format!(
+            "SELECT id, book_id, started_on, finished_on, rating, format, created_at, updated_at \
+             FROM read_throughs WHERE book_id = $1 ORDER BY {}",
+            READ_THROUGH_ORDER
+        );
+        let rows = sqlx::query(&query)
+            .bind(book_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.iter().map(row_to_read_through).collect())
+    }
+
+    pub async fn list_all_read_throughs(&self) -> ApiResult<Vec<ReadThrough>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, book_id, started_on, finished_on, rating, format, created_at, updated_at
+            FROM read_throughs
+            ORDER BY book_id, id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(row_to_read_through).collect())
+    }
+
+    pub async fn get_read_through_by_id(&self, id: i32) -> ApiResult<ReadThrough> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, book_id, started_on, finished_on, rating, format, created_at, updated_at
+            FROM read_throughs
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| read_through_not_found(id))?;
+
+        Ok(row_to_read_through(&row))
+    }
+
+    /// Start or log a read-through. The book's status and `date_finished`
+    /// follow its latest read-through.
+    pub async fn create_read_through(
+        &self,
+        book_id: i32,
+        request: CreateReadThroughRequest,
+    ) -> ApiResult<ReadThrough> {
+        // Validate the request using the validator crate
+        request.validate()?;
+        validate_date_range(request.started_on, request.finished_on)?;
+
+        // First check if the parent book exists
+        ensure_book_exists(&self.pool, book_id).await?;
+
+        if request.finished_on.is_none() && self.has_open_read_through(book_id).await? {
+            return Err(read_through_in_progress(book_id));
+        }
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO read_throughs (book_id, started_on, finished_on, rating, format)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, book_id, started_on, finished_on, rating, format, created_at, updated_at
+            "#,
+        )
+        .bind(book_id)
+        .bind(request.started_on)
+        .bind(request.finished_on)
+        .bind(request.rating)
+        .bind(request.format)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| open_read_through_conflict(e, book_id))?;
+
+        Ok(row_to_read_through(&row))
+    }
+
+    /// Update a read-through. Sending `finished_on: null` reopens a finished
+    /// read-through, as long as the book has no other one in progress.
+    pub async fn update_read_through(
+        &self,
+        id: i32,
+        request: UpdateReadThroughRequest,
+    ) -> ApiResult<ReadThrough> {
+        // Validate the request using the validator crate
+        request.validate()?;
+
+        // First check if read-through exists
+        let existing = self.get_read_through_by_id(id).await?;
+        let finished_on = request.finished_on.unwrap_or(existing.finished_on);
+        validate_date_range(request.started_on.or(existing.started_on), finished_on)?;
+
+        let reopen = existing.finished_on.is_some() && finished_on.is_none();
+        if reopen && self.has_open_read_through(existing.book_id).await? {
+            return Err(read_through_in_progress(existing.book_id));
+        }
+
+        let row = sqlx::query(
+            r#"
+            UPDATE read_throughs
+            SET
+                started_on = COALESCE($2, started_on),
+                finished_on = $3,
+                rating = COALESCE($4, rating),
+                format = COALESCE($5, format),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, book_id, started_on, finished_on, rating, format, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(request.started_on)
+        .bind(finished_on)
+        .bind(request.rating)
+        .bind(request.format)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| open_read_through_conflict(e, existing.book_id))?;
+
+        Ok(row_to_read_through(&row))
+    }
+
+    pub async fn delete_read_through(&self, id: i32) -> ApiResult<()> {
+        let result = sqlx::query("DELETE FROM read_throughs WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(read_through_not_found(id));
+        }
+
+        Ok(())
+    }
+
+    async fn has_open_read_through(&self, book_id: i32) -> ApiResult<bool> {
+        let open =
+            sqlx::query("SELECT 1 FROM read_throughs WHERE book_id = $1 AND finished_on IS NULL")
+                .bind(book_id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(open.is_some())
+    }
+}
+
+/// Record a status or finish date written straight onto a book as read-throughs,
+/// so the fields derived from them agree with what was written.
+///
+/// - `Reading`, `Paused` and `Abandoned` start a read-through on `started_on`
+///   unless one is in progress.
+/// - Otherwise `started_on` moves the start of the latest read-through.
+/// - A finish date completes the read-through in progress, or else corrects the
+///   latest finished one, or else logs a new finished read-through.
+/// - `Finished` without a date completes the read-through in progress today.
+/// - `Wishlist` drops the read-through in progress.
+pub(crate) async fn sync_read_throughs(
+    tx: &mut Transaction<'_, Postgres>,
+    book_id: i32,
+    status: Option<BookStatus>,
+    date_finished: Option<NaiveDate>,
+    started_on: Option<NaiveDate>,
+) -> ApiResult<()> {
+    if status == Some(BookStatus::Wishlist) {
+        sqlx::query("DELETE FROM read_throughs WHERE book_id = $1 AND finished_on IS NULL")
+            .bind(book_id)
+            .execute(&mut **tx)
+            .await?;
+    }
+
+    if let Some(date) = date_finished {
+        let recorded = match status {
+            // A finish date alongside `Reading` belongs to an earlier read
+            Some(BookStatus::Reading | BookStatus::Paused | BookStatus::Abandoned) => {
+                finish_latest(tx, book_id, date).await?
+            }
+            Some(BookStatus::Finished) | None => {
+                finish_open(tx, book_id, date).await? || finish_latest(tx, book_id, date).await?
+            }
+            // The date is kept on the book as written
+            Some(BookStatus::Wishlist) => true,
+        };
+        if !recorded {
+            insert_finished(tx, book_id, date).await?;
+        }
+    } else if status == Some(BookStatus::Finished) {
+        finish_open(tx, book_id, Utc::now().date_naive()).await?;
+    }
+
+    if status.is_some_and(BookStatus::has_open_read) {
+        sqlx::query(
+            r#"
+            INSERT INTO read_throughs (book_id, started_on)
+            SELECT $1, $2
+            WHERE NOT EXISTS (SELECT 1 FROM read_throughs WHERE book_id = $1 AND finished_on IS NULL)
+            "#,
+        )
+        .bind(book_id)
+        .bind(started_on)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    if let Some(date) = started_on.filter(|_| status != Some(BookStatus::Wishlist)) {
+        sqlx::query(
+            r#"
+            UPDATE read_throughs
+            SET started_on = $2, updated_at = NOW()
+            WHERE id = (
+                SELECT id FROM read_throughs
+                WHERE book_id = $1
+                ORDER BY finished_on IS NULL DESC, COALESCE(finished_on, started_on) DESC NULLS LAST, id DESC
+                LIMIT 1
+            )
+            AND started_on IS DISTINCT FROM $2
+            AND (finished_on IS NULL OR finished_on >= $2)
+            "#,
+        )
+        .bind(book_id)
+        .bind(date)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Finish the read-through in progress on `date`; false if there is none
+async fn finish_open(
+    tx: &mut Transaction<'_, Postgres>,
+    book_id: i32,
+    date: NaiveDate,
+) -> ApiResult<bool> {
+    let result = sqlx::query(
+        r#"
+        UPDATE read_throughs
+        SET finished_on = $2,
+            started_on = CASE WHEN started_on > $2 THEN $2 ELSE started_on END,
+            updated_at = NOW()
+        WHERE book_id = $1 AND finished_on IS NULL
+        "#,
+    )
+    .bind(book_id)
+    .bind(date)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Move the most recent finish to `date`; false if the book was never finished
+async fn finish_latest(
+    tx: &mut Transaction<'_, Postgres>,
+    book_id: i32,
+    date: NaiveDate,
+) -> ApiResult<bool> {
+    let result = sqlx::query(
+        r#"
+        UPDATE read_throughs
+        SET finished_on = $2,
+            started_on = CASE WHEN started_on > $2 THEN $2 ELSE started_on END,
+            updated_at = NOW()
+        WHERE id = (
+            SELECT id FROM read_throughs
+            WHERE book_id = $1 AND finished_on IS NOT NULL
+            ORDER BY finished_on DESC, id DESC
+            LIMIT 1
+        )
+        "#,
+    )
+    .bind(book_id)
+    .bind(date)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+async fn insert_finished(
+    tx: &mut Transaction<'_, Postgres>,
+    book_id: i32,
+    date: NaiveDate,
+) -> ApiResult<()> {
+    sqlx::query("INSERT INTO read_throughs (book_id, finished_on) VALUES ($1, $2)")
+        .bind(book_id)
+        .bind(date)
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
+}
+
+fn validate_date_range(
+    started_on: Option<NaiveDate>,
+    finished_on: Option<NaiveDate>,
+) -> ApiResult<()> {
+    if let (Some(started_on), Some(finished_on)) = (started_on, finished_on) {
+        if finished_on < started_on {
+            return Err(ApiError::ValidationError(
+                "finished_on: must not be before started_on".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn read_through_not_found(id: i32) -> ApiError {
+    ApiError::NotFound(format!("Read-through with id {} not found", id))
+}
+
+fn read_through_in_progress(book_id: i32) -> ApiError {
+    ApiError::BadRequest(format!(
+        "Book with id {} already has a read-through in progress",
+        book_id
+    ))
+}
+
+/// A concurrent request can start a read-through after the book was checked
+fn open_read_through_conflict(error: sqlx::Error, book_id: i32) -> ApiError {
+    if is_unique_violation(&error) {
+        read_through_in_progress(book_id)
+    } else {
+        error.into()
+    }
+}
+
+pub(crate) fn row_to_read_through(row: &sqlx::postgres::PgRow) -> ReadThrough {
+    ReadThrough {
+        id: row.get("id"),
+        book_id: row.get("book_id"),
+        started_on: row.get("started_on"),
+        finished_on: row.get("finished_on"),
+        rating: row.get("rating"),
+        format: row.get("format"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
@@ -0,0 +1,248 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row};
+use validator::Validate;
+
+use super::ensure_book_exists;
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{
+    CreateReadingSessionRequest, ReadingSession, ReadingSessionFilter, ReadingSessionSummary,
+    UpdateReadingSessionRequest,
+};
+
+/// Columns selected for a session, including its derived duration
+const SESSION_COLUMNS: &str = "id, book_id, started_at, ended_at, \
+     (EXTRACT(EPOCH FROM ended_at - started_at) / 60)::BIGINT AS duration_minutes, \
+     pages_read, location, note, created_at, updated_at";
+
+/// Condition shared by session listings and summaries; binds `$1` book id,
+/// `$2` first day and `$3` last day, each optional. Days are in UTC.
+const SESSION_FILTER: &str = "($1::INTEGER IS NULL OR book_id = $1) \
+     AND ($2::DATE IS NULL OR (started_at AT TIME ZONE 'UTC')::DATE >= $2) \
+     AND ($3::DATE IS NULL OR (started_at AT TIME ZONE 'UTC')::DATE <= $3)";
+
+#[derive(Clone)]
+pub struct ReadingSessionService {
+    pool: PgPool,
+}
+
+impl ReadingSessionService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create_session(
+        &self,
+        book_id: i32,
+        request: CreateReadingSessionRequest,
+    ) -> ApiResult<ReadingSession> {
+        // Validate the request using the validator crate
+        request.validate()?;
+        validate_session_range(request.started_at, request.ended_at)?;
+
+        // First check if the parent book exists
+        ensure_book_exists(&self.pool, book_id).await?;
+
+        let query = format!(
+            r#"
+            INSERT INTO reading_sessions (book_id, started_at, ended_at, pages_read, location, note)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING {}
+            "#,
+            SESSION_COLUMNS
+        );
+        let row = sqlx::query(&query)
+            .bind(book_id)
+            .bind(request.started_at)
+            .bind(request.ended_at)
+            .bind(request.pages_read)
+            .bind(request.location.as_deref().map(str::trim))
+            .bind(request.note.as_deref().map(str::trim))
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row_to_session(&row))
+    }
+
+    pub async fn get_session_by_id(&self, id: i32) -> ApiResult<ReadingSession> {
+        let query = format!(
+            "SELECT {} FROM reading_sessions WHERE id = $1",
+            SESSION_COLUMNS
+        );
+        let row = sqlx::query(&query)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| session_not_found(id))?;
+
+        Ok(row_to_session(&row))
+    }
+
+    /// Sessions for one book, most recent first
+    pub async fn get_sessions_for_book(&self, book_id: i32) -> ApiResult<Vec<ReadingSession>> {
+        ensure_book_exists(&self.pool, book_id).await?;
+
+        self.get_sessions(ReadingSessionFilter {
+            book_id: Some(book_id),
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Sessions matching `filter`, most recent first
+    pub async fn get_sessions(
+        &self,
+        filter: ReadingSessionFilter,
+    ) -> ApiResult<Vec<ReadingSession>> {
+        let query = format!(
+            "SELECT {} FROM reading_sessions WHERE {} ORDER BY started_at DESC, id DESC",
+            SESSION_COLUMNS, SESSION_FILTER
+        );
+        let rows = sqlx::query(&query)
+            .bind(filter.book_id)
+            .bind(filter.from)
+            .bind(filter.to)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.iter().map(row_to_session).collect())
+    }
+
+    /// Time spent, pages per hour and daily streaks over matching sessions.
+    ///
+    /// Days are calendar days in UTC. Streaks are runs of consecutive days with
+    /// at least one session, found by grouping days on `day - row_number`.
+    pub async fn get_summary(
+        &self,
+        filter: ReadingSessionFilter,
+    ) -> ApiResult<ReadingSessionSummary> {
+        let query = format!(
+            r#"
+            WITH matching AS (
+                SELECT * FROM reading_sessions WHERE {}
+            ),
+            days AS (
+                SELECT DISTINCT (started_at AT TIME ZONE 'UTC')::DATE AS day FROM matching
+            ),
+            streaks AS (
+                SELECT MAX(day) AS last_day, COUNT(*) AS length
+                FROM (
+                    SELECT day, day - (ROW_NUMBER() OVER (ORDER BY day))::INTEGER AS streak
+                    FROM days
+                ) AS numbered
+                GROUP BY streak
+            )
+            SELECT
+                (SELECT COUNT(*) FROM matching) AS sessions,
+                (SELECT COALESCE(SUM(EXTRACT(EPOCH FROM ended_at - started_at)), 0)::BIGINT / 60 FROM matching) AS total_minutes,
+                (SELECT COALESCE(SUM(pages_read), 0)::BIGINT FROM matching) AS total_pages,
+                (SELECT SUM(pages_read) / NULLIF(SUM(EXTRACT(EPOCH FROM ended_at - started_at)) / 3600, 0)
+                    FROM matching WHERE pages_read IS NOT NULL)::DOUBLE PRECISION AS pages_per_hour,
+                (SELECT COUNT(*) FROM days) AS days_read,
+                (SELECT COALESCE(MAX(length), 0) FROM streaks
+                    WHERE last_day >= (NOW() AT TIME ZONE 'UTC')::DATE - 1) AS current_streak_days,
+                (SELECT COALESCE(MAX(length), 0) FROM streaks) AS longest_streak_days
+            "#,
+            SESSION_FILTER
+        );
+        let row = sqlx::query(&query)
+            .bind(filter.book_id)
+            .bind(filter.from)
+            .bind(filter.to)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(ReadingSessionSummary {
+            sessions: row.get("sessions"),
+            total_minutes: row.get("total_minutes"),
+            total_pages: row.get("total_pages"),
+            pages_per_hour: row.get("pages_per_hour"),
+            days_read: row.get("days_read"),
+            current_streak_days: row.get("current_streak_days"),
+            longest_streak_days: row.get("longest_streak_days"),
+        })
+    }
+
+    pub async fn update_session(
+        &self,
+        id: i32,
+        request: UpdateReadingSessionRequest,
+    ) -> ApiResult<ReadingSession> {
+        // Validate the request using the validator crate
+        request.validate()?;
+
+        // First check if session exists
+        let existing = self.get_session_by_id(id).await?;
+        validate_session_range(
+            request.started_at.unwrap_or(existing.started_at),
+            request.ended_at.unwrap_or(existing.ended_at),
+        )?;
+
+        let query = format!(
+            r#"
+            UPDATE reading_sessions
+            SET
+                started_at = COALESCE($2, started_at),
+                ended_at = COALESCE($3, ended_at),
+                pages_read = COALESCE($4, pages_read),
+                location = COALESCE($5, location),
+                note = COALESCE($6, note),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            SESSION_COLUMNS
+        );
+        let row = sqlx::query(&query)
+            .bind(id)
+            .bind(request.started_at)
+            .bind(request.ended_at)
+            .bind(request.pages_read)
+            .bind(request.location.as_deref().map(str::trim))
+            .bind(request.note.as_deref().map(str::trim))
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row_to_session(&row))
+    }
+
+    pub async fn delete_session(&self, id: i32) -> ApiResult<()> {
+        let result = sqlx::query("DELETE FROM reading_sessions WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(session_not_found(id));
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_session_range(started_at: DateTime<Utc>, ended_at: DateTime<Utc>) -> ApiResult<()> {
+    if ended_at < started_at {
+        return Err(ApiError::ValidationError(
+            "ended_at: must not be before started_at".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn session_not_found(id: i32) -> ApiError {
+    ApiError::NotFound(format!("Reading session with id {} not found", id))
+}
+
+fn row_to_session(row: &sqlx::postgres::PgRow) -> ReadingSession {
+    ReadingSession {
+        id: row.get("id"),
+        book_id: row.get("book_id"),
+        started_at: row.get("started_at"),
+        ended_at: row.get("ended_at"),
+        duration_minutes: row.get("duration_minutes"),
+        pages_read: row.get("pages_read"),
+        location: row.get("location"),
+        note: row.get("note"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
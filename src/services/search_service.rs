@@ -0,0 +1,80 @@
+use sqlx::{PgPool, Row};
+
+use super::book_query::prefix_tsquery;
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{SearchKind, SearchResult};
+
+/// `ts_headline` options: wrap hits in `<mark>` and keep excerpts short
+const HEADLINE_OPTIONS: &str =
+    "StartSel=<mark>, StopSel=</mark>, MaxWords=35, MinWords=15, MaxFragments=2";
+
+#[derive(Clone)]
+pub struct SearchService {
+    pool: PgPool,
+}
+
+impl SearchService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Full-text search across books, notes and highlights, best matches first.
+    ///
+    /// Every word in `query` is prefix-matched, so `dost crim` finds
+    /// "Crime and Punishment" by Dostoevsky.
+    pub async fn search(&self, query: &str, limit: Option<u32>) -> ApiResult<Vec<SearchResult>> {
+        let tsquery = prefix_tsquery(query).ok_or_else(|| {
+            ApiError::BadRequest("Search query must contain at least one word".to_string())
+        })?;
+        let limit = limit.unwrap_or(20).clamp(1, 100) as i64;
+
+        let rows = sqlx::query(
+            r#"
+            WITH q AS (SELECT to_tsquery('english', $1) AS query)
+            SELECT 'book' AS kind, b.id, b.id AS book_id, b.title AS book_title, b.author AS book_author,
+                   ts_headline('english', concat_ws(' — ', b.title, b.author, b.description), q.query, $2) AS snippet,
+                   ts_rank(b.search_vector, q.query) AS rank
+            FROM books b, q
+            WHERE b.search_vector @@ q.query
+            UNION ALL
+            SELECT 'note', n.id, n.book_id, b.title, b.author,
+                   ts_headline('english', n.content, q.query, $2),
+                   ts_rank(n.search_vector, q.query)
+            FROM notes n JOIN books b ON b.id = n.book_id, q
+            WHERE n.search_vector @@ q.query
+            UNION ALL
+            SELECT 'highlight', h.id, h.book_id, b.title, b.author,
+                   ts_headline('english', h.text, q.query, $2),
+                   ts_rank(h.search_vector, q.query)
+            FROM highlights h JOIN books b ON b.id = h.book_id, q
+            WHERE h.search_vector @@ q.query
+            ORDER BY rank DESC, kind, id
+            LIMIT $3
+            "#,
+        )
+        .bind(tsquery)
+        .bind(HEADLINE_OPTIONS)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let results: Vec<SearchResult> = rows.iter().map(|row| self.row_to_result(row)).collect();
+        Ok(results)
+    }
+
+    fn row_to_result(&self, row: &sqlx::postgres::PgRow) -> SearchResult {
+        SearchResult {
+            kind: match row.get::<Option<String>, _>("kind").as_deref() {
+                Some("note") => SearchKind::Note,
+                Some("highlight") => SearchKind::Highlight,
+                _ => SearchKind::Book,
+            },
+            id: row.get("id"),
+            book_id: row.get("book_id"),
+            book_title: row.get("book_title"),
+            book_author: row.get("book_author"),
+            snippet: row.get("snippet"),
+            rank: row.get("rank"),
+        }
+    }
+}
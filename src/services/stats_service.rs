@@ -0,0 +1,202 @@
+use sqlx::{PgPool, Row};
+
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{BookStatus, FacetCount, PeriodCount, ReadingStats, StatsFilter};
+
+/// Read-throughs finished within the `$1`..=`$2` date range, with their book.
+/// A book read twice in the range counts twice. A read with no known start is
+/// taken to have started when the book was added.
+const FINISHED_BOOKS: &str = r#"
+    finished AS (
+        SELECT books.id, books.author, books.tags, books.page_count,
+               COALESCE(read_throughs.rating, books.rating) AS rating,
+               COALESCE(read_throughs.started_on, books.date_added) AS started_on,
+               read_throughs.finished_on
+        FROM read_throughs
+        JOIN books ON books.id = read_throughs.book_id
+        WHERE read_throughs.finished_on IS NOT NULL
+          AND ($1::DATE IS NULL OR read_throughs.finished_on >= $1)
+          AND ($2::DATE IS NULL OR read_throughs.finished_on <= $2)
+    )
+"#;
+
+const DEFAULT_TOP: i64 = 10;
+const MAX_TOP: i64 = 100;
+
+#[derive(Clone)]
+pub struct StatsService {
+    pool: PgPool,
+}
+
+impl StatsService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get_stats(&self, filter: StatsFilter) -> ApiResult<ReadingStats> {
+        if let (Some(from), Some(to)) = (filter.from, filter.to) {
+            if from > to {
+                return Err(ApiError::BadRequest(
+                    "from must not be after to".to_string(),
+                ));
+            }
+        }
+        let top = filter.top.unwrap_or(DEFAULT_TOP).clamp(1, MAX_TOP);
+
+        let query = format!(
+            r#"
+            WITH {}
+            SELECT
+                COUNT(*) AS books_finished,
+                COALESCE(SUM(page_count), 0)::BIGINT AS pages_read,
+                AVG(rating)::DOUBLE PRECISION AS average_rating,
+                (AVG(finished_on - started_on) FILTER (WHERE started_on <= finished_on))::DOUBLE PRECISION
+                    AS average_days_to_finish
+            FROM finished
+            "#,
+            FINISHED_BOOKS
+        );
+        let row = sqlx::query(&query)
+            .bind(filter.from)
+            .bind(filter.to)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(ReadingStats {
+            books_finished: row.get("books_finished"),
+            pages_read: row.get("pages_read"),
+            average_rating: row.get("average_rating"),
+            average_days_to_finish: row.get("average_days_to_finish"),
+            finished_per_month: self.finished_per_period(&filter, "YYYY-MM").await?,
+            finished_per_year: self.finished_per_period(&filter, "YYYY").await?,
+            rating_distribution: self.rating_distribution(&filter).await?,
+            top_authors: self
+                .top_values(&filter, "SELECT author AS value FROM finished", top)
+                .await?,
+            top_tags: self
+                .top_values(&filter, "SELECT unnest(tags) AS value FROM finished", top)
+                .await?,
+            books_by_status: self.books_by_status().await?,
+        })
+    }
+
+    /// Every book in the library by its current status, including statuses no
+    /// book has
+    async fn books_by_status(&self) -> ApiResult<Vec<FacetCount<BookStatus>>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT status AS value, COUNT(books.id) AS count
+            FROM unnest(enum_range(NULL::book_status)) AS status
+            LEFT JOIN books USING (status)
+            GROUP BY status
+            ORDER BY status
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| FacetCount {
+                value: row.get("value"),
+                count: row.get("count"),
+            })
+            .collect())
+    }
+
+    /// Finished books and pages grouped by `to_char(finished_on, format)`, oldest first
+    async fn finished_per_period(
+        &self,
+        filter: &StatsFilter,
+        format: &str,
+    ) -> ApiResult<Vec<PeriodCount>> {
+        let query = format!(
+            r#"
+            WITH {}
+            SELECT to_char(finished_on, $3) AS period,
+                   COUNT(*) AS books,
+                   COALESCE(SUM(page_count), 0)::BIGINT AS pages
+            FROM finished
+            GROUP BY period
+            ORDER BY period
+            "#,
+            FINISHED_BOOKS
+        );
+        let rows = sqlx::query(&query)
+            .bind(filter.from)
+            .bind(filter.to)
+            .bind(format)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| PeriodCount {
+                period: row.get("period"),
+                books: row.get("books"),
+                pages: row.get("pages"),
+            })
+            .collect())
+    }
+
+    async fn rating_distribution(&self, filter: &StatsFilter) -> ApiResult<Vec<FacetCount<i32>>> {
+        let query = format!(
+            r#"
+            WITH {}
+            SELECT rating AS value, COUNT(finished.id) AS count
+            FROM generate_series(1, 5) AS rating
+            LEFT JOIN finished USING (rating)
+            GROUP BY rating
+            ORDER BY rating
+            "#,
+            FINISHED_BOOKS
+        );
+        let rows = sqlx::query(&query)
+            .bind(filter.from)
+            .bind(filter.to)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| FacetCount {
+                value: row.get("value"),
+                count: row.get("count"),
+            })
+            .collect())
+    }
+
+    /// The `top` most frequent values produced by `values`, ties broken alphabetically
+    async fn top_values(
+        &self,
+        filter: &StatsFilter,
+        values: &str,
+        top: i64,
+    ) -> ApiResult<Vec<FacetCount<String>>> {
+        let query = format!(
+            r#"
+            WITH {}
+            SELECT value, COUNT(*) AS count
+            FROM ({}) AS finished_values
+            GROUP BY value
+            ORDER BY count DESC, value
+            LIMIT $3
+            "#,
+            FINISHED_BOOKS, values
+        );
+        let rows = sqlx::query(&query)
+            .bind(filter.from)
+            .bind(filter.to)
+            .bind(top)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| FacetCount {
+                value: row.get("value"),
+                count: row.get("count"),
+            })
+            .collect())
+    }
+}
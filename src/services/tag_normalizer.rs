@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+/// Alias mapped to the canonical tag it stands for
+pub type TagAliases = HashMap<String, String>;
+
+/// Separator between levels of a hierarchical tag, e.g. `fiction/sci-fi`
+pub const TAG_SEPARATOR: char = '/';
+
+/// Rewrites tags into their canonical form before they are stored or queried.
+///
+/// Tags are trimmed, lower-cased and have runs of whitespace collapsed, and
+/// each `/`-separated level is cleaned the same way. A configured alias then
+/// replaces the whole tag, so `scifi` can be stored as `fiction/sci-fi`.
+#[derive(Debug, Clone, Default)]
+pub struct TagNormalizer {
+    aliases: TagAliases,
+}
+
+impl TagNormalizer {
+    /// Build a normalizer from `alias -> canonical tag` pairs. Both sides are
+    /// normalized, so aliases match regardless of case or spacing.
+    pub fn new(aliases: TagAliases) -> Self {
+        let aliases = aliases
+            .iter()
+            .filter_map(|(alias, tag)| Some((clean_tag(alias)?, clean_tag(tag)?)))
+            .collect();
+
+        Self { aliases }
+    }
+
+    /// Parse aliases written as `alias=tag` pairs separated by commas,
+    /// e.g. `scifi=fiction/sci-fi, sf=fiction/sci-fi`
+    pub fn parse_aliases(spec: &str) -> Result<TagAliases, String> {
+        spec.split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| match pair.split_once('=') {
+                Some((alias, tag)) if !alias.trim().is_empty() && !tag.trim().is_empty() => {
+                    Ok((alias.trim().to_string(), tag.trim().to_string()))
+                }
+                _ => Err(format!("Invalid tag alias '{}', expected alias=tag", pair)),
+            })
+            .collect()
+    }
+
+    /// Canonical form of a single tag, or `None` if nothing is left of it
+    pub fn normalize(&self, tag: &str) -> Option<String> {
+        let tag = clean_tag(tag)?;
+        Some(self.aliases.get(&tag).cloned().unwrap_or(tag))
+    }
+
+    /// Normalize every tag, dropping blanks and duplicates but keeping order
+    pub fn normalize_all<S: AsRef<str>>(&self, tags: &[S]) -> Vec<String> {
+        let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+        for tag in tags.iter().filter_map(|tag| self.normalize(tag.as_ref())) {
+            if !normalized.contains(&tag) {
+                normalized.push(tag);
+            }
+        }
+        normalized
+    }
+}
+
+/// Trim, case-fold and tidy each level of a tag, dropping empty levels
+fn clean_tag(tag: &str) -> Option<String> {
+    let levels: Vec<String> = tag
+        .trim()
+        .trim_start_matches('#')
+        .split(TAG_SEPARATOR)
+        .map(|level| {
+            level
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_lowercase()
+        })
+        .filter(|level| !level.is_empty())
+        .collect();
+
+    (!levels.is_empty()).then(|| levels.join(&TAG_SEPARATOR.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aliases(pairs: &[(&str, &str)]) -> TagAliases {
+        pairs
+            .iter()
+            .map(|(alias, tag)| (alias.to_string(), tag.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn normalize_cleans_each_level() {
+        let tags = TagNormalizer::default();
+
+        assert_eq!(tags.normalize("  Sci-Fi "), Some("sci-fi".to_string()));
+        assert_eq!(
+            tags.normalize("#Fiction /  Space   Opera/"),
+            Some("fiction/space opera".to_string())
+        );
+        assert_eq!(tags.normalize("//a//b/"), Some("a/b".to_string()));
+        assert_eq!(tags.normalize("   "), None);
+        assert_eq!(tags.normalize("#/ /"), None);
+    }
+
+    #[test]
+    fn normalize_applies_aliases_after_cleaning() {
+        let tags = TagNormalizer::new(aliases(&[(" SciFi ", "Fiction/Sci-Fi"), ("blank", "  ")]));
+
+        assert_eq!(tags.normalize("scifi"), Some("fiction/sci-fi".to_string()));
+        assert_eq!(tags.normalize("#SCIFI"), Some("fiction/sci-fi".to_string()));
+        // Aliases replace whole tags only, and ones without a target are ignored
+        assert_eq!(
+            tags.normalize("scifi/classic"),
+            Some("scifi/classic".to_string())
+        );
+        assert_eq!(tags.normalize("blank"), Some("blank".to_string()));
+    }
+
+    #[test]
+    fn normalize_all_drops_blanks_and_duplicates_in_order() {
+        let tags = TagNormalizer::new(aliases(&[("sf", "fiction/sci-fi")]));
+
+        assert_eq!(
+            tags.normalize_all(&["History", " ", "sf", "history", "Fiction/Sci-Fi", "poetry"]),
+            vec!["history", "fiction/sci-fi", "poetry"]
+        );
+        assert!(tags.normalize_all::<&str>(&[]).is_empty());
+    }
+
+    #[test]
+    fn parse_aliases_reads_comma_separated_pairs() {
+        assert_eq!(
+            TagNormalizer::parse_aliases(" scifi = fiction/sci-fi, sf=fiction/sci-fi ,, "),
+            Ok(aliases(&[
+                ("scifi", "fiction/sci-fi"),
+                ("sf", "fiction/sci-fi")
+            ]))
+        );
+        assert_eq!(TagNormalizer::parse_aliases(""), Ok(TagAliases::new()));
+
+        for invalid in ["scifi", "=fiction", "scifi= ", "a=b, c"] {
+            assert!(
+                TagNormalizer::parse_aliases(invalid).is_err(),
+                "{}",
+                invalid
+            );
+        }
+    }
+}
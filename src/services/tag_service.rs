@@ -0,0 +1,178 @@
+use sqlx::{PgPool, Row};
+use validator::Validate;
+
+use super::tag_normalizer::TagNormalizer;
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{MergeTagsRequest, RenameTagRequest, TagChangeReport, TagUsage};
+
+#[derive(Clone)]
+pub struct TagService {
+    pool: PgPool,
+    tags: TagNormalizer,
+}
+
+impl TagService {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            tags: TagNormalizer::default(),
+        }
+    }
+
+    /// Normalize the tags looked up and written with `tags`
+    pub fn with_tag_normalizer(mut self, tags: TagNormalizer) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Rewrite stored tags that are not in canonical form, such as ones an
+    /// alias configured since maps elsewhere. Returns the books updated.
+    ///
+    /// Books are read without locking and rewritten in one statement; a book
+    /// whose tags changed in between is left as it is.
+    pub async fn normalize_stored_tags(&self) -> ApiResult<u64> {
+        let rows = sqlx::query("SELECT id, tags FROM books WHERE cardinality(tags) > 0")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let changes: Vec<serde_json::Value> = rows
+            .iter()
+            .filter_map(|row| {
+                let tags: Vec<String> = row.get("tags");
+                let normalized = self.tags.normalize_all(&tags);
+                (normalized != tags).then(|| {
+                    serde_json::json!({
+                        "id": row.get::<i32, _>("id"),
+                        "tags": tags,
+                        "normalized": normalized,
+                    })
+                })
+            })
+            .collect();
+        if changes.is_empty() {
+            return Ok(0);
+        }
+
+        let result = sqlx::query(
+            r#"
+            UPDATE books
+            SET tags = ARRAY(SELECT jsonb_array_elements_text(changes.normalized))
+            FROM jsonb_to_recordset($1::text::jsonb) AS changes(id INTEGER, tags JSONB, normalized JSONB)
+            WHERE books.id = changes.id
+              AND books.tags = ARRAY(SELECT jsonb_array_elements_text(changes.tags))
+            "#,
+        )
+        .bind(serde_json::Value::from(changes).to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Every tag in the library with its usage count, most used first
+    pub async fn list_tags(&self) -> ApiResult<Vec<TagUsage>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT tag, COUNT(*) AS book_count
+            FROM books, unnest(tags) AS tag
+            GROUP BY tag
+            ORDER BY book_count DESC, tag
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| TagUsage {
+                name: row.get("tag"),
+                book_count: row.get("book_count"),
+            })
+            .collect())
+    }
+
+    /// Rename `tag` on every book. Renaming onto an existing tag merges the two.
+    pub async fn rename_tag(
+        &self,
+        tag: &str,
+        request: RenameTagRequest,
+    ) -> ApiResult<TagChangeReport> {
+        request.validate()?;
+        self.replace_tags(&[tag.to_string()], &request.name).await
+    }
+
+    /// Replace each of `sources` with `target` on every book
+    pub async fn merge_tags(&self, request: MergeTagsRequest) -> ApiResult<TagChangeReport> {
+        request.validate()?;
+        self.replace_tags(&request.sources, &request.target).await
+    }
+
+    /// Remove `tag` from every book
+    pub async fn delete_tag(&self, tag: &str) -> ApiResult<TagChangeReport> {
+        let tag = self.tags.normalize(tag).ok_or_else(|| tag_not_found(tag))?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let result =
+            sqlx::query("UPDATE books SET tags = array_remove(tags, $1) WHERE $1 = ANY(tags)")
+                .bind(&tag)
+                .execute(&mut *tx)
+                .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(tag_not_found(&tag));
+        }
+
+        tx.commit().await?;
+
+        Ok(TagChangeReport {
+            books_updated: result.rows_affected(),
+        })
+    }
+
+    /// Swap `sources` for `target` in one statement, keeping each book's tag
+    /// order and dropping the duplicates a merge would create
+    async fn replace_tags(&self, sources: &[String], target: &str) -> ApiResult<TagChangeReport> {
+        let target = self
+            .tags
+            .normalize(target)
+            .ok_or_else(|| ApiError::ValidationError("Tag must not be blank".to_string()))?;
+        let sources = self.tags.normalize_all(sources);
+
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE books
+            SET tags = ARRAY(
+                    SELECT renamed
+                    FROM (
+                        SELECT CASE WHEN tag = ANY($1) THEN $2 ELSE tag END AS renamed, position
+                        FROM unnest(tags) WITH ORDINALITY AS t(tag, position)
+                    ) AS renamed_tags
+                    GROUP BY renamed
+                    ORDER BY MIN(position)
+                )
+            WHERE tags && $1
+            "#,
+        )
+        .bind(&sources)
+        .bind(target)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(tag_not_found(&sources.join(", ")));
+        }
+
+        tx.commit().await?;
+
+        Ok(TagChangeReport {
+            books_updated: result.rows_affected(),
+        })
+    }
+}
+
+fn tag_not_found(tag: &str) -> ApiError {
+    ApiError::NotFound(format!("No books are tagged {}", tag))
+}
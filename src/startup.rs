@@ -8,10 +8,11 @@ use crate::{
     server::create_app,
     telemetry::{init_telemetry, TelemetryError},
 };
-use book_notes::AppState;
+use book_notes::{ApiError, AppState, BookService, TagNormalizer, TagService};
 
 /// Application startup and lifecycle management
 pub struct Application {
+    #[allow(dead_code)]
     config: Config,
     socket_addr: SocketAddr,
     app_state: AppState,
@@ -40,7 +41,7 @@ impl Application {
             .map_err(ApplicationError::DatabaseHealth)?;
 
         // Create application state
-        let app_state = AppState::new(pool);
+        let app_state = AppState::new(pool, TagNormalizer::new(config.tag_aliases.clone()));
 
         // Parse the socket address
         let socket_addr: SocketAddr = config
@@ -58,6 +59,7 @@ impl Application {
     }
 
     /// Get the socket address the server will bind to
+    #[allow(dead_code)]
     pub fn socket_addr(&self) -> SocketAddr {
         self.socket_addr
     }
@@ -87,6 +89,7 @@ impl Application {
     }
 
     /// Run the application in the background (useful for testing)
+    #[allow(dead_code)]
     pub async fn run_until_stopped(self) -> Result<(), ApplicationError> {
         let app = create_app(self.app_state);
 
@@ -102,6 +105,47 @@ impl Application {
     }
 }
 
+/// Recompute `notes_count` for every book and exit
+#[instrument(name = "repair_notes_counts")]
+pub async fn repair_notes_counts() -> Result<u64, ApplicationError> {
+    let config = Config::from_env().map_err(ApplicationError::Config)?;
+
+    init_telemetry(&config).map_err(ApplicationError::Telemetry)?;
+
+    let pool = Database::connect(&config)
+        .await
+        .map_err(ApplicationError::Database)?;
+
+    let repaired = BookService::new(pool)
+        .recompute_notes_counts()
+        .await
+        .map_err(ApplicationError::Maintenance)?;
+
+    info!("Recomputed notes counts, {} book(s) corrected", repaired);
+    Ok(repaired)
+}
+
+/// Rewrite stored tags with the configured aliases and exit
+#[instrument(name = "normalize_tags")]
+pub async fn normalize_tags() -> Result<u64, ApplicationError> {
+    let config = Config::from_env().map_err(ApplicationError::Config)?;
+
+    init_telemetry(&config).map_err(ApplicationError::Telemetry)?;
+
+    let pool = Database::connect(&config)
+        .await
+        .map_err(ApplicationError::Database)?;
+
+    let retagged = TagService::new(pool)
+        .with_tag_normalizer(TagNormalizer::new(config.tag_aliases))
+        .normalize_stored_tags()
+        .await
+        .map_err(ApplicationError::Maintenance)?;
+
+    info!("Normalized tags, {} book(s) retagged", retagged);
+    Ok(retagged)
+}
+
 /// Wait for shutdown signal (Ctrl+C or SIGTERM)
 async fn shutdown_signal() {
     let ctrl_c = async {
@@ -149,4 +193,6 @@ pub enum ApplicationError {
     InvalidSocketAddress,
     #[error("Server runtime error: {0}")]
     Server(std::io::Error),
+    #[error("Maintenance task failed: {0}")]
+    Maintenance(ApiError),
 }